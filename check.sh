@@ -1,4 +1,4 @@
 #!/usr/bin/env bash
 
 # Format, then check with all possible combinations of features, finally execute unit tests
-cargo fmt && cargo clippy && cargo clippy --all-features && cargo clippy --features train && cargo clippy --features serde && cargo t --all-features
\ No newline at end of file
+cargo fmt && cargo clippy && cargo clippy --all-features && cargo clippy --features backprop && cargo clippy --features optimisers && cargo clippy --features random && cargo clippy --features train && cargo clippy --features serde && cargo t --all-features
\ No newline at end of file
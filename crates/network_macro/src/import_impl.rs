@@ -0,0 +1,27 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, Type};
+
+/// Generates `ImportableNetwork`, converting each dense layer into the layer in the same position
+pub fn generate_importable_impl(
+    name: &Ident,
+    generics: &Generics,
+    num_type: &Type,
+    names: &[Ident],
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let layer_count = names.len();
+    let indices = 0..names.len();
+
+    quote! {
+        impl #impl_generics neural_thingamajigy::import::ImportableNetwork for #name #ty_generics #where_clause{
+            fn from_dense_layers(layers: &[neural_thingamajigy::import::DenseLayer]) -> Result<Self, neural_thingamajigy::data::DataError>{
+                neural_thingamajigy::import::check_dense_layers(layers, #layer_count)?;
+
+                Ok(Self{
+                    #(#names: neural_thingamajigy::import::dense_layer::<#num_type, _, _>(&layers[#indices])?),*
+                })
+            }
+        }
+    }
+}
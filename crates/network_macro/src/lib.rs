@@ -1,54 +1,160 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse::Parse, parse_macro_input, Ident, LitInt, Token, Type, Visibility};
+use syn::{
+    parse::Parse, parse_macro_input, parse_quote, Generics, Ident, LitInt, Token, Type, Visibility,
+};
 
+#[cfg(feature = "std-train")]
+mod import_impl;
 mod network_impl;
-#[cfg(feature = "train")]
+#[cfg(feature = "random")]
 mod random_impl;
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 mod trainable_impl;
 
 struct LayerChainParams {
     visibility: Visibility,
     num_type: Type,
     name: Ident,
+    /// Empty, or the number type parameter of a generic network bounded by `RealField + Copy`
+    generics: Generics,
     layers: Vec<LitInt>,
+    /// The name of the layer outputting each width after the first
+    names: Vec<Ident>,
 }
 
+/// The methods generated on every network, which layers can't be named as they'd clash with
+/// their accessors
+const RESERVED_NAMES: [&str; 1] = ["cast"];
+
 impl Parse for LayerChainParams {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let visibility: Visibility = input.parse()?;
 
         let name = input.parse()?;
+        let mut generics: Generics = input.parse()?;
         input.parse::<Token![,]>()?;
-        let num_type = input.parse()?;
-        input.parse::<Token![,]>()?;
+
+        if generics.params.len() > 1 || generics.lifetimes().next().is_some() {
+            return Err(syn::Error::new_spanned(
+                generics,
+                "networks can only be generic over their number type",
+            ));
+        }
+        let num_type = match generics.type_params_mut().next() {
+            Some(param) => {
+                param.bounds.push(parse_quote!(nalgebra::RealField));
+                param.bounds.push(parse_quote!(Copy));
+                let ident = &param.ident;
+                parse_quote!(#ident)
+            }
+            None => {
+                let num_type = input.parse()?;
+                input.parse::<Token![,]>()?;
+                num_type
+            }
+        };
 
         let mut hidden = Vec::new();
+        let mut layer_names = Vec::new();
+
+        loop {
+            let layer_name: Option<Ident> = if input.peek(Ident) && input.peek2(Token![:]) {
+                let layer_name = input.parse()?;
+                input.parse::<Token![:]>()?;
+                Some(layer_name)
+            } else {
+                None
+            };
+            let width = match (input.parse::<LitInt>(), &layer_name) {
+                (Ok(width), _) => width,
+                (Err(error), Some(_)) => return Err(error),
+                (Err(_), None) => break,
+            };
+            if let (Some(layer_name), true) = (&layer_name, hidden.is_empty()) {
+                return Err(syn::Error::new_spanned(
+                    layer_name,
+                    "the first width is the inputs, which isn't a layer so can't be named",
+                ));
+            }
 
-        while let Ok(width) = input.parse::<LitInt>() {
             let mut reps = 1;
             if input.peek(Token![*]) {
                 _ = input.parse::<Token![*]>();
 
                 let count = input.parse::<LitInt>()?;
                 reps = count.base10_parse()?;
+                if let (Some(layer_name), true) = (&layer_name, reps != 1) {
+                    return Err(syn::Error::new_spanned(
+                        layer_name,
+                        "repeated widths can't be named",
+                    ));
+                }
             }
 
             for _ in 0..reps {
                 hidden.push(width.clone());
+                layer_names.push(layer_name.clone());
             }
 
             _ = input.parse::<Token![,]>();
         }
 
+        let names = resolve_names(&layer_names)?;
+
         Ok(Self {
             visibility,
             name,
             num_type,
+            generics,
             layers: hidden,
+            names,
+        })
+    }
+}
+
+/// Names each layer, with its given name or `layer0`, `layer1`, ... by position, failing if a
+/// name is used twice or is reserved
+fn resolve_names(layer_names: &[Option<Ident>]) -> syn::Result<Vec<Ident>> {
+    let names: Vec<_> = layer_names
+        .iter()
+        .skip(1)
+        .enumerate()
+        .map(|(i, layer_name)| {
+            layer_name
+                .clone()
+                .unwrap_or_else(|| format_ident!("layer{}", i))
         })
+        .collect();
+
+    // only given names have a span to point at, and a default name can only clash with them
+    for (i, layer_name) in layer_names.iter().skip(1).enumerate() {
+        let Some(layer_name) = layer_name else {
+            continue;
+        };
+
+        if RESERVED_NAMES.contains(&layer_name.to_string().as_str()) {
+            return Err(syn::Error::new_spanned(
+                layer_name,
+                format!(
+                    "`{}` is a method of every network, so can't name a layer",
+                    layer_name
+                ),
+            ));
+        }
+        if names
+            .iter()
+            .enumerate()
+            .any(|(j, other)| j != i && other == layer_name)
+        {
+            return Err(syn::Error::new_spanned(
+                layer_name,
+                format!("there is more than one layer named `{}`", layer_name),
+            ));
+        }
     }
+
+    Ok(names)
 }
 
 /// Creates a network with the supplied visibility, name and width arguments.
@@ -58,13 +164,16 @@ impl Parse for LayerChainParams {
 ///     - `VISIBILITY`, is the visibility *prefix* that the generated network will have. e.g: `pub` or (empty)
 ///     - `NAME`, is the identifier for the generated network. e.g: `MyNetwork`, `WeatherPredictor`, `Critic`
 ///     - `TYPE`, is the type of number used in the generated network. Must `impl nalgebra::RealField + Copy`.
-///         e.g: `f32` or `f64`
+///         e.g: `f32` or `f64`. Omitted when `NAME` takes a type parameter, see [Generic form](#generic-form)
 ///     - `WIDTHS`, the comma seperated list of layer widths, there are 2 formats: `N`, will
 ///         produce a single layer with N nodes. `N * M` will produce `M` layers each with `N` nodes.
 ///         Thus: `5, 5, 5, 6` and `5 * 3, 6` will produce the same network. The first layer width will
 ///         be the `INPUTS` generic on the produced network wheras the last layer width will be the
-///         `OUTPUTS` generic.
+///         `OUTPUTS` generic. Any width after the first can be prefixed with `NAME:` to name the layer
+///         producing it, otherwise layers are named `layer0`, `layer1`, ...
 ///
+/// Every layer can be read using the method with its name, and the network implements
+/// `NetworkInfo`, which describes its layers.
 ///
 /// # Examples
 /// ```rust
@@ -84,7 +193,43 @@ impl Parse for LayerChainParams {
 ///     // One is a lot easier to read & type.
 ///     network!(pub CoolName, f32, 5, 5, 5, 6);
 ///     network!(pub CoolerName, f32, 5 * 3, 6);
-///     
+///
+/// ```
+/// ## Named layers
+/// ```rust
+///     use neural_thingamajigy::{network, NetworkInfo, RandomisableNetwork};
+///
+///     // The layers are called `hidden`, `layer1` and `output`
+///     network!(pub Named, f32, 2, hidden: 5, 5, output: 1);
+///     let network = Named::random(&mut rand::rngs::OsRng);
+///
+///     let hidden: &neural_thingamajigy::Layer<f32, 2, 5> = network.hidden();
+///     assert_eq!(network.layer_info().map(|l| l.name).collect::<Vec<_>>(), ["hidden", "layer1", "output"]);
+/// ```
+/// Each name must be unique, including the default names of the other layers
+/// ```compile_fail
+///     use neural_thingamajigy::network;
+///
+///     // the second layer is already called `layer1`
+///     network!(pub Clashing, f32, 2, layer1: 3, 4);
+/// ```
+/// and can't be `cast`, which is a method of every network
+/// ```compile_fail
+///     use neural_thingamajigy::network;
+///
+///     network!(pub Reserved, f32, 2, cast: 3, 1);
+/// ```
+/// ## Generic form
+/// Replacing `TYPE` with a type parameter on the name makes the network generic over its number
+/// type, and adds `cast` to convert between them.
+/// ```rust
+///     use neural_thingamajigy::{network, Network, activators::Relu, RandomisableNetwork};
+///
+///     network!(pub Precise<T>, 2, 5, 1);
+///     // e.g. train in f64, then deploy in f32
+///     let trained = Precise::<f64>::random(&mut rand::rngs::OsRng);
+///     let deployed: Precise<f32> = trained.cast();
+///     let output = deployed.evaluate(nalgebra::Vector2::new(1f32, 1f32), &Relu::default());
 /// ```
 ///
 /// # Panics
@@ -98,37 +243,54 @@ pub fn network(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
         visibility,
         num_type,
         name,
+        generics,
         layers,
+        names,
     } = parse_macro_input!(tokens);
     if layers.len() < 2 {
         panic!("You need to supply at least 2 layer width parameters");
     }
 
-    let (struct_definiton, inputs, outputs, names) =
-        generate_struct_definition(&visibility, &name, &num_type, &layers);
-
-    let network_impl = network_impl::generate_network_impl(&num_type, &layers, &names, &name);
-
-    #[cfg(feature = "train")]
-    let (trainable_network_impl, random_impl) = (
-        trainable_impl::generate_trainable_network_impl(
-            &visibility,
-            &name,
-            &names,
-            &inputs,
-            &outputs,
-            &num_type,
-        ),
-        random_impl::generate_random_impl(&name, &num_type, &names),
+    let struct_definiton =
+        generate_struct_definition(&visibility, &name, &generics, &num_type, &layers, &names);
+
+    let network_impl =
+        network_impl::generate_network_impl(&num_type, &layers, &names, &name, &generics);
+    let cast_impl = network_impl::generate_cast_impl(&num_type, &names, &name, &generics);
+    let info_impl = network_impl::generate_info_impl(&layers, &names, &name, &generics);
+
+    #[cfg(feature = "backprop")]
+    let trainable_network_impl = trainable_impl::generate_trainable_network_impl(
+        &visibility,
+        &name,
+        &names,
+        &layers[..layers.len() - 1],
+        &layers[1..],
+        &num_type,
+        &generics,
     );
-    #[cfg(not(feature = "train"))]
-    let (trainable_network_impl, random_impl) = (quote! {}, quote! {});
+    #[cfg(not(feature = "backprop"))]
+    let trainable_network_impl = quote! {};
+
+    #[cfg(feature = "std-train")]
+    let importable_impl =
+        import_impl::generate_importable_impl(&name, &generics, &num_type, &names);
+    #[cfg(not(feature = "std-train"))]
+    let importable_impl = quote! {};
+
+    #[cfg(feature = "random")]
+    let random_impl = random_impl::generate_random_impl(&name, &generics, &num_type, &names);
+    #[cfg(not(feature = "random"))]
+    let random_impl = quote! {};
 
     let emitted_code = quote! {
         #struct_definiton
         #network_impl
+        #cast_impl
+        #info_impl
         #trainable_network_impl
         #random_impl
+        #importable_impl
     };
 
     emitted_code.into()
@@ -137,32 +299,35 @@ pub fn network(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
 fn generate_struct_definition(
     visibility: &Visibility,
     name: &Ident,
+    generics: &Generics,
     num_type: &Type,
     layers: &[LitInt],
-) -> (TokenStream, Vec<LitInt>, Vec<LitInt>, Vec<Ident>) {
+    names: &[Ident],
+) -> TokenStream {
     #[cfg(not(feature = "serde"))]
     let serde = quote! {};
     #[cfg(feature = "serde")]
     let serde = quote! {#[derive(serde::Deserialize, serde::Serialize)]};
 
-    let inputs: Vec<_> = layers.to_vec();
-    let outputs: Vec<_> = layers.iter().skip(1).cloned().collect();
-    let names: Vec<_> = (0usize..)
-        .take(outputs.len())
-        .map(|i| format_ident!("layer{}", i))
-        .collect();
+    let (inputs, outputs) = (&layers[..layers.len() - 1], &layers[1..]);
+    let docs = names
+        .iter()
+        .map(|layer_name| format!("The `{}` layer of the network", layer_name));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    assert_eq!(inputs.len(), outputs.len() + 1);
+    quote! {
+        #serde
+        #visibility struct #name #generics {
+            #(#names: neural_thingamajigy::Layer<#num_type, #inputs, #outputs>), *
+        }
 
-    (
-        quote! {
-            #serde
-            #visibility struct #name {
-                #(#names: neural_thingamajigy::Layer<#num_type, #inputs, #outputs>), *
-            }
-        },
-        inputs,
-        outputs,
-        names,
-    )
+        impl #impl_generics #name #ty_generics #where_clause{
+            #(
+                #[doc = #docs]
+                pub fn #names(&self) -> &neural_thingamajigy::Layer<#num_type, #inputs, #outputs>{
+                    &self.#names
+                }
+            )*
+        }
+    }
 }
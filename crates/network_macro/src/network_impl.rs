@@ -1,15 +1,17 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Ident, LitInt, Type};
+use syn::{Generics, Ident, LitInt, Type};
 
 pub fn generate_network_impl(
     num_type: &Type,
     layers: &[LitInt],
     names: &Vec<Ident>,
     name: &Ident,
+    generics: &Generics,
 ) -> TokenStream {
     let network_inputs = layers.first().unwrap();
     let network_outputs = layers.last().unwrap();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let input_variable_name: Vec<_> = [format_ident!("inputs")]
         .iter()
@@ -24,7 +26,7 @@ pub fn generate_network_impl(
         .collect();
 
     quote! {
-        impl neural_thingamajigy::Network<#num_type, #network_inputs, #network_outputs> for #name{
+        impl #impl_generics neural_thingamajigy::Network<#num_type, #network_inputs, #network_outputs> for #name #ty_generics #where_clause{
             fn evaluate(
                 &self,
                 inputs: nalgebra::SVector<#num_type, #network_inputs>,
@@ -34,6 +36,68 @@ pub fn generate_network_impl(
 
                 outputs
             }
+
+            fn evaluate_batch<const B: usize>(
+                &self,
+                inputs: nalgebra::SMatrix<#num_type, #network_inputs, B>,
+                activator: &impl neural_thingamajigy::activators::Activator<#num_type>,
+            ) -> nalgebra::SMatrix<#num_type, #network_outputs, B>{
+                #(let #output_variable_name = self.#names.through_batch(#input_variable_name, activator);)*
+
+                outputs
+            }
+        }
+    }
+}
+
+/// Generates `cast` for networks that are generic over their number type
+pub fn generate_cast_impl(
+    num_type: &Type,
+    names: &[Ident],
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    if generics.params.is_empty() {
+        return quote! {};
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // the target type mustn't shadow the network's own parameter
+    let target = match quote!(#num_type).to_string().as_str() {
+        "U" => format_ident!("V"),
+        _ => format_ident!("U"),
+    };
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause{
+            /// Converts every weight and bias to another number type(via f64), e.g. to deploy a
+            /// network trained in f64 as f32
+            pub fn cast<#target: nalgebra::RealField + Copy>(&self) -> #name<#target>{
+                #name{
+                    #(#names: self.#names.cast()),*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `NetworkInfo`, describing each layer by its field name
+pub fn generate_info_impl(
+    layers: &[LitInt],
+    names: &[Ident],
+    name: &Ident,
+    generics: &Generics,
+) -> TokenStream {
+    let network_inputs = layers.first().unwrap();
+    let network_outputs = layers.last().unwrap();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name_strings = names.iter().map(|i| i.to_string());
+
+    quote! {
+        impl #impl_generics neural_thingamajigy::NetworkInfo<#network_inputs, #network_outputs> for #name #ty_generics #where_clause{
+            fn layer_info(&self) -> impl Iterator<Item = neural_thingamajigy::LayerInfo>{
+                [#(self.#names.info(#name_strings)),*].into_iter()
+            }
         }
     }
 }
@@ -1,10 +1,20 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Ident, Type};
+use syn::{Generics, Ident, Type};
+
+pub fn generate_random_impl(
+    name: &Ident,
+    generics: &Generics,
+    num_type: &Type,
+    names: &[Ident],
+) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
 
-pub fn generate_random_impl(name: &Ident, num_type: &Type, names: &[Ident]) -> TokenStream {
     quote! {
-        impl neural_thingamajigy::RandomisableNetwork<#num_type> for #name{
+        impl #impl_generics neural_thingamajigy::RandomisableNetwork<#num_type> for #name #ty_generics
+        where
+            rand::distributions::Standard: rand::distributions::Distribution<#num_type>,
+        {
             fn random(rng: &mut impl rand::Rng) -> Self{
                 Self{
                     #(#names: neural_thingamajigy::Layer::random(rng)),*
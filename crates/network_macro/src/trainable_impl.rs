@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Ident, LitInt, Type, Visibility};
+use syn::{parse_quote, Generics, Ident, LitInt, Type, Visibility};
 
 mod gradient;
 
@@ -11,13 +11,15 @@ pub fn generate_trainable_network_impl(
     inputs: &[LitInt],
     outputs: &[LitInt],
     num_type: &Type,
+    generics: &Generics,
 ) -> TokenStream {
     let (network_layer_inputs_impl, network_layer_inputs_name) =
-        generate_trainable_network_inputs(visibility, name, names, inputs, num_type);
+        generate_trainable_network_inputs(visibility, name, names, inputs, num_type, generics);
     let (network_gradient_impl, network_gradient_impl_name) =
         gradient::generate_trainable_network_gradient(
-            visibility, name, names, inputs, outputs, num_type,
+            visibility, name, names, inputs, outputs, num_type, generics,
         );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let network_inputs = inputs.first().unwrap();
     let network_outputs = outputs.last().unwrap();
@@ -27,10 +29,31 @@ pub fn generate_trainable_network_impl(
     let get_gradient_impl =
         generate_get_gradient_impl(num_type, network_inputs, network_outputs, names);
     let apply_nudge_impl = generate_apply_nudge_impl(names);
+    let batch_trainable_network_impl = generate_batch_trainable_network_impl(
+        visibility,
+        name,
+        names,
+        inputs,
+        num_type,
+        network_inputs,
+        network_outputs,
+        generics,
+    );
+    #[cfg(feature = "std-train")]
+    let prunable_network_impl = generate_prunable_network_impl(
+        name,
+        names,
+        num_type,
+        network_inputs,
+        network_outputs,
+        generics,
+    );
+    #[cfg(not(feature = "std-train"))]
+    let prunable_network_impl = quote! {};
     quote! {
         #network_layer_inputs_impl
         #network_gradient_impl
-        impl neural_thingamajigy::TrainableNetwork<#num_type, #network_inputs, #network_outputs> for #name{
+        impl #impl_generics neural_thingamajigy::TrainableNetwork<#num_type, #network_inputs, #network_outputs> for #name #ty_generics #where_clause{
             type LayerInputs = #network_layer_inputs_name;
             type Gradient = #network_gradient_impl_name;
 
@@ -40,6 +63,8 @@ pub fn generate_trainable_network_impl(
 
             #apply_nudge_impl
         }
+        #batch_trainable_network_impl
+        #prunable_network_impl
     }
 }
 
@@ -49,16 +74,18 @@ fn generate_trainable_network_inputs(
     names: &[Ident],
     inputs: &[LitInt],
     num_type: &Type,
-) -> (TokenStream, Ident) {
+    generics: &Generics,
+) -> (TokenStream, TokenStream) {
     let inputs_name = format_ident!("{}LayerInputs", name);
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
 
     (
         quote! {
-            #visibility struct #inputs_name{
+            #visibility struct #inputs_name #generics #where_clause{
                 #(#names: nalgebra::SVector<#num_type, #inputs>), *
             }
         },
-        inputs_name,
+        quote! {#inputs_name #ty_generics},
     )
 }
 
@@ -123,6 +150,18 @@ fn generate_get_gradient_impl(
                 current_loss_gradient
             )
         }
+
+        fn get_input_gradient(
+            &self,
+            layer_inputs: &Self::LayerInputs,
+            output_loss_gradients: nalgebra::SVector<#num_type, #network_outputs>,
+            activator: &impl neural_thingamajigy::activators::Activator<#num_type>,
+        ) -> nalgebra::SVector<#num_type, #network_inputs>{
+            let current_loss_gradient = output_loss_gradients;
+            #(let current_loss_gradient = self.#reversed_names.backpropogate_inputs(current_loss_gradient, layer_inputs.#reversed_names, activator);)*
+
+            current_loss_gradient
+        }
     }
 }
 
@@ -133,3 +172,120 @@ fn generate_apply_nudge_impl(names: &[Ident]) -> TokenStream {
         }
     }
 }
+
+#[expect(clippy::too_many_arguments)]
+fn generate_batch_trainable_network_impl(
+    visibility: &Visibility,
+    name: &Ident,
+    names: &[Ident],
+    inputs: &[LitInt],
+    num_type: &Type,
+    network_inputs: &LitInt,
+    network_outputs: &LitInt,
+    generics: &Generics,
+) -> TokenStream {
+    let inputs_name = format_ident!("{}BatchLayerInputs", name);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut batch_generics = generics.clone();
+    batch_generics.params.push(parse_quote!(const B: usize));
+    let (_, batch_ty_generics, _) = batch_generics.split_for_impl();
+    let reversed_names: Vec<_> = names.iter().rev().collect();
+
+    let input_variable_name: Vec<_> = [format_ident!("inputs")]
+        .iter()
+        .cloned()
+        .chain(names.iter().skip(1).map(|i| format_ident!("{}_input", i)))
+        .collect();
+    let output_variable_name: Vec<_> = names
+        .iter()
+        .skip(1)
+        .map(|i| format_ident!("{}_input", i))
+        .chain([format_ident!("outputs")].iter().cloned())
+        .collect();
+
+    quote! {
+        #visibility struct #inputs_name #batch_generics #where_clause{
+            #(#names: nalgebra::SMatrix<#num_type, #inputs, B>), *
+        }
+
+        impl #impl_generics neural_thingamajigy::BatchTrainableNetwork<#num_type, #network_inputs, #network_outputs> for #name #ty_generics #where_clause{
+            type BatchLayerInputs<const B: usize> = #inputs_name #batch_ty_generics;
+
+            fn evaluate_training_batch<const B: usize>(
+                &self,
+                inputs: nalgebra::SMatrix<#num_type, #network_inputs, B>,
+                activator: &impl neural_thingamajigy::activators::Activator<#num_type>,
+            ) -> (nalgebra::SMatrix<#num_type, #network_outputs, B>, Self::BatchLayerInputs<B>){
+                #(let #output_variable_name = self.#names.through_batch(#input_variable_name, activator);)*
+
+                let all_inputs = #inputs_name{
+                    #(#names: #input_variable_name),*
+                };
+
+                (outputs, all_inputs)
+            }
+
+            fn get_gradient_batch<const B: usize>(
+                &self,
+                layer_inputs: &Self::BatchLayerInputs<B>,
+                output_loss_gradients: nalgebra::SMatrix<#num_type, #network_outputs, B>,
+                activator: &impl neural_thingamajigy::activators::Activator<#num_type>,
+            ) -> (Self::Gradient, nalgebra::SMatrix<#num_type, #network_inputs, B>){
+                let current_loss_gradient = output_loss_gradients;
+                #(let (#reversed_names, current_loss_gradient) = self.#reversed_names.backpropogate_batch(current_loss_gradient, layer_inputs.#reversed_names, activator);)*
+
+                (
+                    Self::Gradient{
+                        #(#reversed_names),*
+                    },
+                    current_loss_gradient
+                )
+            }
+
+            fn get_input_gradient_batch<const B: usize>(
+                &self,
+                layer_inputs: &Self::BatchLayerInputs<B>,
+                output_loss_gradients: nalgebra::SMatrix<#num_type, #network_outputs, B>,
+                activator: &impl neural_thingamajigy::activators::Activator<#num_type>,
+            ) -> nalgebra::SMatrix<#num_type, #network_inputs, B>{
+                let current_loss_gradient = output_loss_gradients;
+                #(let current_loss_gradient = self.#reversed_names.backpropogate_inputs_batch(current_loss_gradient, layer_inputs.#reversed_names, activator);)*
+
+                current_loss_gradient
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std-train")]
+fn generate_prunable_network_impl(
+    name: &Ident,
+    names: &[Ident],
+    num_type: &Type,
+    network_inputs: &LitInt,
+    network_outputs: &LitInt,
+    generics: &Generics,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let indices: Vec<_> = (0..names.len())
+        .map(proc_macro2::Literal::usize_unsuffixed)
+        .collect();
+
+    quote! {
+        impl #impl_generics neural_thingamajigy::pruning::PrunableNetwork<#num_type, #network_inputs, #network_outputs> for #name #ty_generics #where_clause{
+            fn inspect_weights(&self, f: &mut impl FnMut(usize, &#num_type)){
+                #(self.#names.inspect_weights(&mut |w| f(#indices, w));)*
+            }
+
+            fn map_weights(&mut self, f: &mut impl FnMut(usize, &#num_type) -> #num_type){
+                #(self.#names.map_weights(&mut |w| f(#indices, w));)*
+            }
+
+            fn mask(&self) -> Self::Gradient{
+                Self::Gradient{
+                    #(#names: self.#names.mask()),*
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Ident, LitInt, Type, Visibility};
+use syn::{Generics, Ident, LitInt, Type, Visibility};
 
 pub fn generate_trainable_network_gradient(
     visibility: &Visibility,
@@ -9,16 +9,34 @@ pub fn generate_trainable_network_gradient(
     inputs: &[LitInt],
     outputs: &[LitInt],
     num_type: &Type,
-) -> (TokenStream, Ident) {
+    generics: &Generics,
+) -> (TokenStream, TokenStream) {
     let inputs_name = format_ident!("{}Gradient", name);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    #[cfg(not(feature = "serde"))]
+    let serde = quote! {};
+    #[cfg(feature = "serde")]
+    let serde = quote! {#[derive(serde::Deserialize, serde::Serialize)]};
+
+    let layer_count = names.len();
+    let indices: Vec<_> = (0..names.len()).collect();
 
     (
         quote! {
-            #[derive(Default)]
-            #visibility struct #inputs_name{
+            #serde
+            #visibility struct #inputs_name #generics #where_clause{
                 #(#names: neural_thingamajigy::LayerGradient<#num_type, #inputs, #outputs>), *
             }
-            impl neural_thingamajigy::ValueSet<#num_type> for #inputs_name{
+            // derive(Default) would also require the number type to be Default
+            impl #impl_generics Default for #inputs_name #ty_generics #where_clause{
+                fn default() -> Self {
+                    Self{
+                        #(#names: Default::default()),*
+                    }
+                }
+            }
+            impl #impl_generics neural_thingamajigy::ValueSet<#num_type> for #inputs_name #ty_generics #where_clause{
                 fn unary_operation(&self, f: impl Fn(&#num_type) -> #num_type) -> Self {
                     Self{
                         #(#names: self.#names.unary_operation(&f)),*
@@ -35,6 +53,17 @@ pub fn generate_trainable_network_gradient(
                 fn binary_inspection(&self, other: &Self, f: &mut impl FnMut(&#num_type, &#num_type)) {
                     #(self.#names.binary_inspection(&other.#names, f);)*
                 }
+                fn parts() -> usize {
+                    #layer_count
+                }
+                fn indexed_inspection(&self, f: &mut impl FnMut(usize, &#num_type)) {
+                    #(self.#names.unary_inspection(&mut |v| f(#indices, v));)*
+                }
+                fn indexed_operation(&self, f: impl Fn(usize, &#num_type) -> #num_type) -> Self {
+                    Self{
+                        #(#names: self.#names.unary_operation(|v| f(#indices, v))),*
+                    }
+                }
                 fn all(v: #num_type) -> Self{
                     Self{
                         #(#names: neural_thingamajigy::LayerGradient::all(v)),*
@@ -42,6 +71,6 @@ pub fn generate_trainable_network_gradient(
                 }
             }
         },
-        inputs_name,
+        quote! {#inputs_name #ty_generics},
     )
 }
@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use nalgebra::SVector;
+use neural_thingamajigy::{
+    activators::Elu, loss::squared_error, network, optimiser::AdamOptimiser, train, train_batched,
+    RandomisableNetwork,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+network!(Wide, f32, 16, 32, 32, 4);
+
+/// Compares 1 epoch of per-sample training with batched training
+fn batch_training(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let mut network = Wide::random(&mut rng);
+    let mut opt = AdamOptimiser::default();
+    let data: Vec<_> = (0..256)
+        .map(|_| {
+            (
+                SVector::<f32, 16>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+                SVector::<f32, 4>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+            )
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("train 256 samples");
+    group.bench_function("per sample", |b| {
+        b.iter(|| train(data.iter(), &mut network, &Elu, &squared_error, &mut opt))
+    });
+    group.bench_function("batches of 32", |b| {
+        b.iter(|| {
+            train_batched::<32, _, _, 16, 4>(&data, &mut network, &Elu, &squared_error, &mut opt)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, batch_training);
+criterion_main!(benches);
@@ -0,0 +1,104 @@
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use nalgebra::{RealField, SVector};
+use neural_thingamajigy::{activators::Sigmoid, network, Layer, Network, RandomisableNetwork};
+use rand::{distributions::Standard, prelude::Distribution, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::hint::black_box;
+
+network!(Width8F32, f32, 8, 8, 8, 8);
+network!(Width8F64, f64, 8, 8, 8, 8);
+network!(Width32F32, f32, 32, 32, 32, 32);
+network!(Width32F64, f64, 32, 32, 32, 32);
+network!(Width64F32, f32, 64, 64, 64, 64);
+network!(Width64F64, f64, 64, 64, 64, 64);
+
+network!(Depth2F32, f32, 16, 16, 16);
+network!(Depth2F64, f64, 16, 16, 16);
+network!(Depth4F32, f32, 16, 16, 16, 16, 16);
+network!(Depth4F64, f64, 16, 16, 16, 16, 16);
+network!(Depth8F32, f32, 16, 16, 16, 16, 16, 16, 16, 16, 16);
+network!(Depth8F64, f64, 16, 16, 16, 16, 16, 16, 16, 16, 16);
+
+/// Benchmarks evaluating network on a random input, with the id "{type}/{parameter}" so f32 and
+/// f64 are plotted next to each other
+fn bench_evaluate<T, N, const INPUTS: usize, const OUTPUTS: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    num_type: &str,
+    parameter: usize,
+    rng: &mut impl Rng,
+) where
+    T: RealField + Copy,
+    N: Network<T, INPUTS, OUTPUTS> + RandomisableNetwork<T>,
+    Standard: Distribution<T>,
+{
+    let network = N::random(rng);
+    let inputs = SVector::<T, INPUTS>::from_fn(|_, _| rng.gen());
+
+    group.bench_function(BenchmarkId::new(num_type, parameter), |b| {
+        b.iter(|| network.evaluate(black_box(inputs), &Sigmoid))
+    });
+}
+
+/// Benchmarks a single layer passing inputs through
+fn bench_through<T, const INPUTS: usize, const OUTPUTS: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    num_type: &str,
+    rng: &mut impl Rng,
+) where
+    T: RealField + Copy,
+    Standard: Distribution<T>,
+{
+    let layer = Layer::<T, INPUTS, OUTPUTS>::random(rng);
+    let inputs = SVector::<T, INPUTS>::from_fn(|_, _| rng.gen());
+
+    group.bench_function(BenchmarkId::new(num_type, INPUTS), |b| {
+        b.iter(|| layer.through(black_box(inputs), &Sigmoid))
+    });
+}
+
+/// Compares a square layer at several widths
+fn layer_through(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+    let mut group = c.benchmark_group("Layer::through");
+    bench_through::<f32, 8, 8>(&mut group, "f32", &mut rng);
+    bench_through::<f64, 8, 8>(&mut group, "f64", &mut rng);
+    bench_through::<f32, 32, 32>(&mut group, "f32", &mut rng);
+    bench_through::<f64, 32, 32>(&mut group, "f64", &mut rng);
+    bench_through::<f32, 64, 64>(&mut group, "f32", &mut rng);
+    bench_through::<f64, 64, 64>(&mut group, "f64", &mut rng);
+    group.finish();
+}
+
+/// Compares 3 layer networks of several widths
+fn evaluate_width(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+
+    let mut group = c.benchmark_group("evaluate by width");
+    bench_evaluate::<f32, Width8F32, 8, 8>(&mut group, "f32", 8, &mut rng);
+    bench_evaluate::<f64, Width8F64, 8, 8>(&mut group, "f64", 8, &mut rng);
+    bench_evaluate::<f32, Width32F32, 32, 32>(&mut group, "f32", 32, &mut rng);
+    bench_evaluate::<f64, Width32F64, 32, 32>(&mut group, "f64", 32, &mut rng);
+    bench_evaluate::<f32, Width64F32, 64, 64>(&mut group, "f32", 64, &mut rng);
+    bench_evaluate::<f64, Width64F64, 64, 64>(&mut group, "f64", 64, &mut rng);
+    group.finish();
+}
+
+/// Compares networks 16 wide with several numbers of layers
+fn evaluate_depth(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+
+    let mut group = c.benchmark_group("evaluate by depth");
+    bench_evaluate::<f32, Depth2F32, 16, 16>(&mut group, "f32", 2, &mut rng);
+    bench_evaluate::<f64, Depth2F64, 16, 16>(&mut group, "f64", 2, &mut rng);
+    bench_evaluate::<f32, Depth4F32, 16, 16>(&mut group, "f32", 4, &mut rng);
+    bench_evaluate::<f64, Depth4F64, 16, 16>(&mut group, "f64", 4, &mut rng);
+    bench_evaluate::<f32, Depth8F32, 16, 16>(&mut group, "f32", 8, &mut rng);
+    bench_evaluate::<f64, Depth8F64, 16, 16>(&mut group, "f64", 8, &mut rng);
+    group.finish();
+}
+
+criterion_group!(benches, layer_through, evaluate_width, evaluate_depth);
+criterion_main!(benches);
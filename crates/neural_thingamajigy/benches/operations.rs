@@ -0,0 +1,72 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use nalgebra::{RealField, SVector};
+use neural_thingamajigy::{
+    activators::Linear,
+    operations::{Exp, LayerNorm, MinMaxScaler, Normalize, RobustScaler, Softmax, StandardScaler},
+    Network, RandomisableNetwork, TrainableNetwork,
+};
+use rand::{distributions::Standard, prelude::Distribution, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::hint::black_box;
+
+/// How many values each operation is applied to
+const WIDTH: usize = 64;
+
+/// Benchmarks evaluate, and evaluate_training followed by get_gradient, for operation on a
+/// random input
+fn bench_operation<T, N>(c: &mut Criterion, name: &str, num_type: &str, operation: N)
+where
+    T: RealField + Copy,
+    N: Network<T, WIDTH, WIDTH> + TrainableNetwork<T, WIDTH, WIDTH>,
+    Standard: Distribution<T>,
+{
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let inputs = SVector::<T, WIDTH>::from_fn(|_, _| rng.gen());
+    let loss_gradient = SVector::<T, WIDTH>::from_fn(|_, _| rng.gen());
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function(BenchmarkId::new("evaluate", num_type), |b| {
+        b.iter(|| operation.evaluate(black_box(inputs), &Linear))
+    });
+    group.bench_function(BenchmarkId::new("backpropogate", num_type), |b| {
+        b.iter(|| {
+            let (_, layer_inputs) = operation.evaluate_training(black_box(inputs), &Linear);
+            operation.get_gradient(&layer_inputs, black_box(loss_gradient), &Linear)
+        })
+    });
+    group.finish();
+}
+
+/// Benchmarks every operation for a number type
+fn bench_operations<T>(c: &mut Criterion, num_type: &str)
+where
+    T: RealField + Copy,
+    Standard: Distribution<T>,
+{
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    let data: Vec<SVector<T, WIDTH>> = (0..100)
+        .map(|_| SVector::from_fn(|_, _| rng.gen()))
+        .collect();
+
+    bench_operation(c, "Exp", num_type, Exp);
+    bench_operation(c, "Normalize", num_type, Normalize);
+    bench_operation(c, "Softmax", num_type, Softmax);
+    bench_operation(
+        c,
+        "LayerNorm",
+        num_type,
+        LayerNorm::<T, 8>::random(&mut rng),
+    );
+    bench_operation(c, "StandardScaler", num_type, StandardScaler::fit(&data));
+    bench_operation(c, "MinMaxScaler", num_type, MinMaxScaler::fit(&data));
+    bench_operation(c, "RobustScaler", num_type, RobustScaler::fit(&data));
+}
+
+/// Compares every operation in f32 and f64
+fn operations(c: &mut Criterion) {
+    bench_operations::<f32>(c, "f32");
+    bench_operations::<f64>(c, "f64");
+}
+
+criterion_group!(benches, operations);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use nalgebra::{RealField, SMatrix, SVector};
+use neural_thingamajigy::{
+    activators::Sigmoid,
+    network,
+    optimiser::{AdamOptimiser, Optimiser},
+    LayerGradient, RandomisableNetwork, TrainableNetwork,
+};
+use rand::{distributions::Standard, prelude::Distribution, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::hint::black_box;
+
+network!(Width8F32, f32, 8, 8, 8, 8);
+network!(Width8F64, f64, 8, 8, 8, 8);
+network!(Width32F32, f32, 32, 32, 32, 32);
+network!(Width32F64, f64, 32, 32, 32, 32);
+
+/// The gradient of 2 64x64 layers, 8320 values
+type LargeValueSet<T> = (LayerGradient<T, 64, 64>, LayerGradient<T, 64, 64>);
+
+/// Benchmarks evaluate_training followed by get_gradient, the work done for each sample while
+/// training
+fn bench_backpropogation<T, N, const INPUTS: usize, const OUTPUTS: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    num_type: &str,
+    width: usize,
+    rng: &mut impl Rng,
+) where
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS> + RandomisableNetwork<T>,
+    Standard: Distribution<T>,
+{
+    let network = N::random(rng);
+    let inputs = SVector::<T, INPUTS>::from_fn(|_, _| rng.gen());
+    let loss_gradient = SVector::<T, OUTPUTS>::from_fn(|_, _| rng.gen());
+
+    group.bench_function(BenchmarkId::new(num_type, width), |b| {
+        b.iter(|| {
+            let (_, layer_inputs) = network.evaluate_training(black_box(inputs), &Sigmoid);
+            network.get_gradient(&layer_inputs, black_box(loss_gradient), &Sigmoid)
+        })
+    });
+}
+
+/// Benchmarks the ADAM optimiser transforming a large gradient
+fn bench_adam<T>(group: &mut BenchmarkGroup<WallTime>, num_type: &str, rng: &mut impl Rng)
+where
+    T: RealField + Copy + From<f32>,
+    Standard: Distribution<T>,
+{
+    let mut optimiser = AdamOptimiser::<T, LargeValueSet<T>>::default();
+    let mut random_layer = || LayerGradient {
+        weight_gradient: SMatrix::from_fn(|_, _| rng.gen()),
+        bias_gradient: SVector::from_fn(|_, _| rng.gen()),
+    };
+    let gradient: LargeValueSet<T> = (random_layer(), random_layer());
+
+    group.bench_function(num_type, |b| {
+        b.iter(|| optimiser.transform(black_box(&gradient)))
+    });
+}
+
+/// Compares a training step for 3 layer networks of several widths
+fn backpropogation(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+    let mut group = c.benchmark_group("evaluate_training + get_gradient");
+    bench_backpropogation::<f32, Width8F32, 8, 8>(&mut group, "f32", 8, &mut rng);
+    bench_backpropogation::<f64, Width8F64, 8, 8>(&mut group, "f64", 8, &mut rng);
+    bench_backpropogation::<f32, Width32F32, 32, 32>(&mut group, "f32", 32, &mut rng);
+    bench_backpropogation::<f64, Width32F64, 32, 32>(&mut group, "f64", 32, &mut rng);
+    group.finish();
+}
+
+/// Compares ADAM on an 8320 value gradient
+fn adam(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+
+    let mut group = c.benchmark_group("AdamOptimiser::transform 8320 values");
+    bench_adam::<f32>(&mut group, "f32", &mut rng);
+    bench_adam::<f64>(&mut group, "f64", &mut rng);
+    group.finish();
+}
+
+criterion_group!(benches, backpropogation, adam);
+criterion_main!(benches);
@@ -44,7 +44,8 @@ fn main() {
             &activator,
             &squared_error, // Use the [mean] squared error activation function.
             &mut opt,
-        );
+        )
+        .unwrap();
         print!("{:.3}, ", mse);
 
         for (x, y) in data {
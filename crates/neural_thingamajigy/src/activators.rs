@@ -1,5 +1,5 @@
 use nalgebra::RealField;
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 use nalgebra::{SMatrix, SVector};
 
 /// Contains an activaton function and it's gradient
@@ -11,7 +11,7 @@ pub trait Activator<T: RealField + Copy> {
     fn activation_gradient(&self, x: T) -> T;
 
     /// The equivalent matrix for the activation function for some set of weighted values.
-    #[cfg(feature = "train")]
+    #[cfg(feature = "backprop")]
     fn activation_gradient_matrix<const OUTPUTS: usize>(
         &self,
         weighted: SVector<T, OUTPUTS>,
@@ -0,0 +1,532 @@
+use core::marker::PhantomData;
+
+use nalgebra::{RealField, SMatrix, SVector};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{activators::Activator, LayerInfo, Network, NetworkInfo};
+
+/// An encoder and a decoder, trained to reconstruct their inputs through a `LATENT` wide
+/// bottleneck. As a network, inputs are encoded then decoded, so it can be trained like any other
+/// network, or with [train_autoencoder].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AutoEncoder<
+    T: RealField + Copy,
+    const INPUTS: usize,
+    const LATENT: usize,
+    E: Network<T, INPUTS, LATENT>,
+    D: Network<T, LATENT, INPUTS>,
+> {
+    /// Encodes inputs into the latent space
+    pub encoder: E,
+    /// Decodes a point in the latent space
+    pub decoder: D,
+
+    /// The type of each value
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _marker: PhantomData<T>,
+}
+
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        E: Network<T, INPUTS, LATENT>,
+        D: Network<T, LATENT, INPUTS>,
+    > AutoEncoder<T, INPUTS, LATENT, E, D>
+{
+    /// Creates an autoencoder from its encoder and decoder
+    pub fn new(encoder: E, decoder: D) -> Self {
+        Self {
+            encoder,
+            decoder,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Encodes inputs into the latent space
+    pub fn encode(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, LATENT> {
+        self.encoder.evaluate(inputs, activator)
+    }
+
+    /// Decodes a point in the latent space
+    pub fn decode(
+        &self,
+        latent: SVector<T, LATENT>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.decoder.evaluate(latent, activator)
+    }
+
+    /// Encodes then decodes inputs
+    pub fn reconstruct(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.decode(self.encode(inputs, activator), activator)
+    }
+
+    /// The loss between inputs and their reconstruction. Inputs unlike the training data
+    /// reconstruct badly, so this can be used as an anomaly score.
+    #[cfg(feature = "backprop")]
+    pub fn reconstruction_error(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+        loss_function: &(impl Loss<T, INPUTS> + ?Sized),
+    ) -> T {
+        loss_function
+            .loss(&inputs, &self.reconstruct(inputs, activator))
+            .0
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        E: Network<T, INPUTS, LATENT>,
+        D: Network<T, LATENT, INPUTS>,
+    > Network<T, INPUTS, INPUTS> for AutoEncoder<T, INPUTS, LATENT, E, D>
+{
+    fn evaluate(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.reconstruct(inputs, activator)
+    }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        self.decoder
+            .evaluate_batch(self.encoder.evaluate_batch(inputs, activator), activator)
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        E: Network<T, INPUTS, LATENT> + NetworkInfo<INPUTS, LATENT>,
+        D: Network<T, LATENT, INPUTS> + NetworkInfo<LATENT, INPUTS>,
+    > NetworkInfo<INPUTS, INPUTS> for AutoEncoder<T, INPUTS, LATENT, E, D>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        self.encoder.layer_info().chain(self.decoder.layer_info())
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::BatchTrainableNetwork;
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        E: Network<T, INPUTS, LATENT> + TrainableNetwork<T, INPUTS, LATENT>,
+        D: Network<T, LATENT, INPUTS> + TrainableNetwork<T, LATENT, INPUTS>,
+    > TrainableNetwork<T, INPUTS, INPUTS> for AutoEncoder<T, INPUTS, LATENT, E, D>
+{
+    type LayerInputs = (E::LayerInputs, D::LayerInputs);
+
+    type Gradient = (E::Gradient, D::Gradient);
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, INPUTS>, Self::LayerInputs) {
+        let (latent, encoder_inputs) = self.encoder.evaluate_training(inputs, activator);
+        let (outputs, decoder_inputs) = self.decoder.evaluate_training(latent, activator);
+
+        (outputs, (encoder_inputs, decoder_inputs))
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SVector<T, INPUTS>) {
+        let (decoder_gradient, latent_loss_gradient) =
+            self.decoder
+                .get_gradient(&layer_inputs.1, output_loss_gradients, activator);
+        let (encoder_gradient, input_loss_gradient) =
+            self.encoder
+                .get_gradient(&layer_inputs.0, latent_loss_gradient, activator);
+
+        ((encoder_gradient, decoder_gradient), input_loss_gradient)
+    }
+
+    fn get_input_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        let latent_loss_gradient =
+            self.decoder
+                .get_input_gradient(&layer_inputs.1, output_loss_gradients, activator);
+
+        self.encoder
+            .get_input_gradient(&layer_inputs.0, latent_loss_gradient, activator)
+    }
+
+    fn apply_nudge(&mut self, nudge: Self::Gradient) {
+        self.encoder.apply_nudge(nudge.0);
+        self.decoder.apply_nudge(nudge.1);
+    }
+}
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        E: Network<T, INPUTS, LATENT> + BatchTrainableNetwork<T, INPUTS, LATENT>,
+        D: Network<T, LATENT, INPUTS> + BatchTrainableNetwork<T, LATENT, INPUTS>,
+    > BatchTrainableNetwork<T, INPUTS, INPUTS> for AutoEncoder<T, INPUTS, LATENT, E, D>
+{
+    type BatchLayerInputs<const B: usize> = (E::BatchLayerInputs<B>, D::BatchLayerInputs<B>);
+
+    fn evaluate_training_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (SMatrix<T, INPUTS, B>, Self::BatchLayerInputs<B>) {
+        let (latent, encoder_inputs) = self.encoder.evaluate_training_batch(inputs, activator);
+        let (outputs, decoder_inputs) = self.decoder.evaluate_training_batch(latent, activator);
+
+        (outputs, (encoder_inputs, decoder_inputs))
+    }
+
+    fn get_gradient_batch<const B: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<B>,
+        output_loss_gradients: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SMatrix<T, INPUTS, B>) {
+        let (decoder_gradient, latent_loss_gradient) =
+            self.decoder
+                .get_gradient_batch(&layer_inputs.1, output_loss_gradients, activator);
+        let (encoder_gradient, input_loss_gradient) =
+            self.encoder
+                .get_gradient_batch(&layer_inputs.0, latent_loss_gradient, activator);
+
+        ((encoder_gradient, decoder_gradient), input_loss_gradient)
+    }
+
+    fn get_input_gradient_batch<const B: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<B>,
+        output_loss_gradients: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        let latent_loss_gradient = self.decoder.get_input_gradient_batch(
+            &layer_inputs.1,
+            output_loss_gradients,
+            activator,
+        );
+
+        self.encoder
+            .get_input_gradient_batch(&layer_inputs.0, latent_loss_gradient, activator)
+    }
+}
+
+/// A variational autoencoder. The encoder outputs the mean and log variance of a normal
+/// distribution over the latent space(so `STATS` must be `2 * LATENT`), which is sampled and
+/// decoded while training. This makes the latent space smooth, so [Self::generate] can create
+/// new samples.
+///
+/// As a network, the mean is decoded, so it is deterministic.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VariationalAutoEncoder<
+    T: RealField + Copy,
+    const INPUTS: usize,
+    const LATENT: usize,
+    const STATS: usize,
+    E: Network<T, INPUTS, STATS>,
+    D: Network<T, LATENT, INPUTS>,
+> {
+    /// Encodes inputs to the mean(first `LATENT` values) and log variance(last `LATENT` values)
+    pub encoder: E,
+    /// Decodes a point in the latent space
+    pub decoder: D,
+    /// How much the KL divergence from a standard normal distribution adds to the loss
+    pub kl_weight: T,
+}
+
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        const STATS: usize,
+        E: Network<T, INPUTS, STATS>,
+        D: Network<T, LATENT, INPUTS>,
+    > VariationalAutoEncoder<T, INPUTS, LATENT, STATS, E, D>
+{
+    /// Creates a variational autoencoder with a kl_weight of 1
+    pub fn new(encoder: E, decoder: D) -> Self {
+        const { Self::check_stats() };
+
+        Self {
+            encoder,
+            decoder,
+            kl_weight: T::one(),
+        }
+    }
+
+    /// Panics at compile time if STATS isn't `2 * LATENT`
+    const fn check_stats() {
+        assert!(
+            STATS == 2 * LATENT,
+            "the encoder must output a mean and log variance for each latent value"
+        );
+    }
+
+    /// Encodes inputs into the mean and log variance of their distribution in the latent space
+    pub fn encode(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, LATENT>, SVector<T, LATENT>) {
+        const { Self::check_stats() };
+
+        let stats = self.encoder.evaluate(inputs, activator);
+        (
+            stats.fixed_rows::<LATENT>(0).into_owned(),
+            stats.fixed_rows::<LATENT>(LATENT).into_owned(),
+        )
+    }
+
+    /// Decodes a point in the latent space
+    pub fn decode(
+        &self,
+        latent: SVector<T, LATENT>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.decoder.evaluate(latent, activator)
+    }
+
+    /// Decodes the mean of the inputs' distribution in the latent space
+    pub fn reconstruct(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.decode(self.encode(inputs, activator).0, activator)
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        const STATS: usize,
+        E: Network<T, INPUTS, STATS>,
+        D: Network<T, LATENT, INPUTS>,
+    > Network<T, INPUTS, INPUTS> for VariationalAutoEncoder<T, INPUTS, LATENT, STATS, E, D>
+{
+    fn evaluate(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.reconstruct(inputs, activator)
+    }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        const { Self::check_stats() };
+
+        let stats = self.encoder.evaluate_batch(inputs, activator);
+        self.decoder
+            .evaluate_batch(stats.fixed_rows::<LATENT>(0).into_owned(), activator)
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        const STATS: usize,
+        E: Network<T, INPUTS, STATS> + NetworkInfo<INPUTS, STATS>,
+        D: Network<T, LATENT, INPUTS> + NetworkInfo<LATENT, INPUTS>,
+    > NetworkInfo<INPUTS, INPUTS> for VariationalAutoEncoder<T, INPUTS, LATENT, STATS, E, D>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        self.encoder.layer_info().chain(self.decoder.layer_info())
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::{loss::Loss, TrainableNetwork};
+#[cfg(feature = "std-train")]
+use {
+    crate::{
+        apply_accumulated, backprop::non_finite_part, optimiser::Optimiser,
+        random::standard_normal, train::accumulate_samples, valueset::Accumulator, TrainError,
+    },
+    rand::Rng,
+};
+
+#[cfg(feature = "std-train")]
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const LATENT: usize,
+        const STATS: usize,
+        E: Network<T, INPUTS, STATS>,
+        D: Network<T, LATENT, INPUTS>,
+    > VariationalAutoEncoder<T, INPUTS, LATENT, STATS, E, D>
+{
+    /// Decodes a random point from a standard normal distribution, creating a new sample
+    pub fn generate(
+        &self,
+        rng: &mut impl Rng,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.decode(standard_normal(rng), activator)
+    }
+}
+
+/// Perform 1 training epoch on an autoencoder(or any network with the same inputs and outputs),
+/// teaching it to reconstruct every input in data. Returns the average reconstruction loss.
+#[cfg(feature = "std-train")]
+pub fn train_autoencoder<
+    'a,
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, INPUTS>,
+    const INPUTS: usize,
+>(
+    data: impl Iterator<Item = &'a SVector<T, INPUTS>>,
+    network: &mut N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, INPUTS> + ?Sized),
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+) -> Result<T, TrainError> {
+    let mut accumulator = Accumulator::new();
+
+    let total_loss = accumulate_samples(
+        data.map(|x| (x, x, T::one())),
+        network,
+        activator,
+        loss_function,
+        &mut accumulator,
+    )?;
+    let count = accumulator.count();
+
+    apply_accumulated(network, optimiser, &mut accumulator)?;
+
+    Ok(total_loss / nalgebra::convert(count as f64))
+}
+
+/// Perform 1 training epoch on a variational autoencoder, returning the average loss. Each
+/// sample's latent point is drawn from its encoded distribution using rng(the
+/// reparameterisation trick), and its loss is the reconstruction loss plus `kl_weight` times
+/// the KL divergence of its distribution from a standard normal distribution.
+#[cfg(feature = "std-train")]
+pub fn train_variational<
+    'a,
+    T: RealField + Copy,
+    const INPUTS: usize,
+    const LATENT: usize,
+    const STATS: usize,
+    E: TrainableNetwork<T, INPUTS, STATS> + Network<T, INPUTS, STATS>,
+    D: TrainableNetwork<T, LATENT, INPUTS> + Network<T, LATENT, INPUTS>,
+>(
+    data: impl Iterator<Item = &'a SVector<T, INPUTS>>,
+    network: &mut VariationalAutoEncoder<T, INPUTS, LATENT, STATS, E, D>,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, INPUTS> + ?Sized),
+    optimiser: &mut impl Optimiser<T, (E::Gradient, D::Gradient)>,
+    rng: &mut impl Rng,
+) -> Result<T, TrainError> {
+    let half: T = nalgebra::convert(0.5);
+    let kl_weight = network.kl_weight;
+    let mut accumulator = Accumulator::<T, (E::Gradient, D::Gradient)>::new();
+    let mut total_loss = T::zero();
+
+    for (sample, x) in data.enumerate() {
+        let (stats, encoder_inputs) = network.encoder.evaluate_training(*x, activator);
+        let mean = stats.fixed_rows::<LATENT>(0).into_owned();
+        let log_variance = stats.fixed_rows::<LATENT>(LATENT).into_owned();
+
+        // the reparameterisation trick, so the noise doesn't block the gradient
+        let noise = standard_normal::<T, LATENT>(rng);
+        let deviation = log_variance.map(|v| (v * half).exp());
+        let latent = mean + deviation.component_mul(&noise);
+
+        let (reconstructed, decoder_inputs) = network.decoder.evaluate_training(latent, activator);
+        let (reconstruction_loss, loss_gradient) = loss_function.loss(x, &reconstructed);
+
+        let variance = log_variance.map(|v| v.exp());
+        let divergence = (mean.component_mul(&mean) + variance - log_variance)
+            .add_scalar(-T::one())
+            .sum()
+            * half;
+
+        let instance_loss = reconstruction_loss + kl_weight * divergence;
+        if !instance_loss.is_finite() {
+            return Err(TrainError::NonFiniteLoss { sample });
+        }
+        total_loss += instance_loss;
+
+        let (decoder_gradient, latent_gradient) =
+            network
+                .decoder
+                .get_gradient(&decoder_inputs, loss_gradient, activator);
+
+        let mean_gradient = latent_gradient + mean * kl_weight;
+        let log_variance_gradient = (latent_gradient
+            .component_mul(&noise)
+            .component_mul(&deviation)
+            + variance.add_scalar(-T::one()) * kl_weight)
+            * half;
+
+        let mut stats_gradient = SVector::<T, STATS>::zeros();
+        stats_gradient
+            .fixed_rows_mut::<LATENT>(0)
+            .copy_from(&mean_gradient);
+        stats_gradient
+            .fixed_rows_mut::<LATENT>(LATENT)
+            .copy_from(&log_variance_gradient);
+
+        let (encoder_gradient, _) =
+            network
+                .encoder
+                .get_gradient(&encoder_inputs, stats_gradient, activator);
+
+        accumulator.add(&(encoder_gradient, decoder_gradient));
+    }
+
+    let count = accumulator.count();
+    if count == 0 {
+        return Err(TrainError::EmptyDataset);
+    }
+
+    let gradient = accumulator.mean();
+    if let Some(layer) = non_finite_part(&gradient) {
+        return Err(TrainError::NonFiniteGradient { layer: Some(layer) });
+    }
+
+    let (encoder_step, decoder_step) = optimiser.transform(&gradient);
+    network.encoder.apply_nudge(encoder_step);
+    network.decoder.apply_nudge(decoder_step);
+
+    Ok(total_loss / nalgebra::convert(count as f64))
+}
@@ -0,0 +1,178 @@
+use crate::{activators::Activator, network::TrainableNetwork, valueset::ValueSet};
+use loss::Loss;
+use nalgebra::{RealField, SVector};
+#[cfg(feature = "optimisers")]
+use optimiser::Optimiser;
+
+/// Defines TrainError, returned when training fails
+mod error;
+/// Defines the Loss trait and some common losses
+pub mod loss;
+/// Defines Optimiser trait and ADAM
+#[cfg(feature = "optimisers")]
+pub mod optimiser;
+
+pub use {crate::layer::LayerGradient, error::TrainError};
+
+/// Performs 1 training step on data without allocating, so networks can be fine-tuned on
+/// devices without std. gradient is only used to sum the gradient of every sample, so it can
+/// live wherever the caller likes(e.g. a static), and its previous contents are ignored. This
+/// returns the average loss of every sample, and gives the same result as [crate::train] up to
+/// rounding.
+/// ## Example
+/// ```rust
+///     use nalgebra::{Vector1, Vector2};
+///     use neural_thingamajigy::{
+///         activators::Relu, loss::squared_error, network, optimiser::AdamOptimiser, train_step,
+///         RandomisableNetwork,
+///     };
+///
+///     network!(pub MyNetwork, f32, 2, 3, 1);
+///     let mut network = MyNetwork::random(&mut rand::rngs::OsRng);
+///     let mut opt = AdamOptimiser::default();
+///     // The generated gradient type, which holds no heap allocations
+///     let mut gradient = MyNetworkGradient::default();
+///
+///     let data = [(Vector2::new(1f32, 0.), Vector1::new(1f32))];
+///     let relu = Relu::default();
+///     train_step(data.iter(), &mut network, &relu, &squared_error, &mut opt, &mut gradient)
+///         .unwrap();
+/// ```
+#[cfg(feature = "optimisers")]
+pub fn train_step<
+    'a,
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &mut N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+    gradient: &mut N::Gradient,
+) -> Result<T, TrainError> {
+    *gradient = N::Gradient::all(T::zero());
+    let mut total_loss = T::zero();
+    let mut count = 0;
+
+    for (x, y) in data {
+        let (loss, sample_gradient) =
+            sample_gradient(*x, y, T::one(), count, &*network, activator, loss_function)?;
+        total_loss += loss;
+        *gradient = gradient.binary_operation(&sample_gradient, |&g, &s| g + s);
+        count += 1;
+    }
+
+    apply_gradient(network, optimiser, gradient, count)?;
+
+    Ok(total_loss / nalgebra::convert(count as f64))
+}
+
+/// Adds the gradient of a single sample to gradient, returning its loss. Calling this for
+/// several samples, then [apply_gradient], trains on them without allocating. sample is the
+/// index of the sample, which is reported if its loss isn't finite.
+pub fn accumulate_sample<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    input: SVector<T, INPUTS>,
+    expected: &SVector<T, OUTPUTS>,
+    network: &N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    gradient: &mut N::Gradient,
+    sample: usize,
+) -> Result<T, TrainError> {
+    let (loss, sample_gradient) = sample_gradient(
+        input,
+        expected,
+        T::one(),
+        sample,
+        network,
+        activator,
+        loss_function,
+    )?;
+    *gradient = gradient.binary_operation(&sample_gradient, |&g, &s| g + s);
+
+    Ok(loss)
+}
+
+/// Nudges the network by the optimised mean of gradient, which is the sum of count samples'
+/// gradients. gradient is always set to zero ready for the next step, even if count is 0 or the
+/// gradient isn't finite, when the network is left unchanged.
+#[cfg(feature = "optimisers")]
+pub fn apply_gradient<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &mut N,
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+    gradient: &mut N::Gradient,
+    count: usize,
+) -> Result<(), TrainError> {
+    let sum = core::mem::replace(gradient, N::Gradient::all(T::zero()));
+    if count == 0 {
+        return Err(TrainError::EmptyDataset);
+    }
+
+    let count: T = nalgebra::convert(count as f64);
+    let mean = sum.unary_operation(|&g| g / count);
+
+    if let Some(layer) = non_finite_part(&mean) {
+        return Err(TrainError::NonFiniteGradient { layer: Some(layer) });
+    }
+
+    network.apply_nudge(optimiser.transform(&mean));
+
+    Ok(())
+}
+
+/// The loss and gradient of a sample, both multiplied by weight. sample is the index of the
+/// sample, for reporting errors.
+pub(crate) fn sample_gradient<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    input: SVector<T, INPUTS>,
+    expected: &SVector<T, OUTPUTS>,
+    weight: T,
+    sample: usize,
+    network: &N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+) -> Result<(T, N::Gradient), TrainError> {
+    let (predicted, training_data) = network.evaluate_training(input, activator);
+
+    let (loss, loss_gradient) = loss_function.loss(expected, &predicted);
+    let (loss, loss_gradient) = (loss * weight, loss_gradient * weight);
+    if !loss.is_finite() {
+        return Err(TrainError::NonFiniteLoss { sample });
+    }
+    if non_finite_part(&loss_gradient).is_some() {
+        return Err(TrainError::NonFiniteGradient { layer: None });
+    }
+
+    let (gradient, _) = network.get_gradient(&training_data, loss_gradient, activator); // discard network input loss as this isn't deep learning
+
+    Ok((loss, gradient))
+}
+
+/// Returns the index of the first part of values containing NaN or infinity
+pub(crate) fn non_finite_part<T: RealField + Copy>(values: &impl ValueSet<T>) -> Option<usize> {
+    let mut found = None;
+    values.indexed_inspection(&mut |part, v| {
+        if found.is_none() && !v.is_finite() {
+            found = Some(part);
+        }
+    });
+
+    found
+}
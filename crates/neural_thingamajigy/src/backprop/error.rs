@@ -0,0 +1,51 @@
+use core::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+/// An error produced while training or evaluating a network
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrainError {
+    /// There were no samples to train on or evaluate
+    EmptyDataset,
+    /// The loss of a sample was NaN or infinite
+    NonFiniteLoss {
+        /// The index of the sample in the data
+        sample: usize,
+    },
+    /// A gradient contained NaN or infinity, the network is left unchanged
+    NonFiniteGradient {
+        /// The index of the layer(or other part of the network's gradient) containing it,
+        /// None if it came from the loss function
+        layer: Option<usize>,
+    },
+    /// Two things that must be the same length weren't
+    ShapeMismatch {
+        /// The length required
+        expected: usize,
+        /// The length found
+        found: usize,
+    },
+}
+
+impl Display for TrainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrainError::EmptyDataset => write!(f, "the dataset is empty"),
+            TrainError::NonFiniteLoss { sample } => {
+                write!(f, "the loss of sample {} is not finite", sample)
+            }
+            TrainError::NonFiniteGradient { layer: Some(layer) } => {
+                write!(f, "the gradient of layer {} is not finite", layer)
+            }
+            TrainError::NonFiniteGradient { layer: None } => {
+                write!(f, "the gradient of the loss function is not finite")
+            }
+            TrainError::ShapeMismatch { expected, found } => {
+                write!(f, "expected a length of {}, but found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for TrainError {}
@@ -0,0 +1,269 @@
+#[cfg(feature = "std-train")]
+extern crate std;
+
+use nalgebra::{RealField, SVector};
+#[cfg(feature = "std-train")]
+use {super::TrainError, std::vec::Vec};
+
+/// A loss, accepting the actual and predicted value (in that order) and providing the loss value & deriviative.
+/// This is implemented for every function that could be a [LossFunction], so a struct is only needed when the
+/// loss has configuration, like [Huber].
+pub trait Loss<T: RealField, const N: usize> {
+    /// Returns the loss of predicted and its deriviative with respect to predicted
+    fn loss(&self, actual: &SVector<T, N>, predicted: &SVector<T, N>) -> (T, SVector<T, N>);
+}
+
+impl<T: RealField, const N: usize, F> Loss<T, N> for F
+where
+    F: Fn(&SVector<T, N>, &SVector<T, N>) -> (T, SVector<T, N>) + ?Sized,
+{
+    fn loss(&self, actual: &SVector<T, N>, predicted: &SVector<T, N>) -> (T, SVector<T, N>) {
+        self(actual, predicted)
+    }
+}
+
+/// A loss function, accepting the actual and predicted value (in that order) and providing the loss value & deriviative
+pub type LossFunction<T, const N: usize> =
+    dyn Fn(&SVector<T, N>, &SVector<T, N>) -> (T, SVector<T, N>);
+
+/// A [LossFunction] that can be shared between threads
+#[cfg(feature = "parallel")]
+pub type SyncLossFunction<T, const N: usize> =
+    dyn Fn(&SVector<T, N>, &SVector<T, N>) -> (T, SVector<T, N>) + Sync;
+
+/// The Squared Error loss function
+pub fn squared_error<T: RealField + Copy, const N: usize>(
+    actual: &SVector<T, N>,
+    predicted: &SVector<T, N>,
+) -> (T, SVector<T, N>) {
+    let delta = predicted - actual;
+    (delta.norm_squared(), delta + delta) // there is no longer a definition of 2, so delta + delta is a workaround for 2 * delta
+}
+
+/// The Absolute Error loss function
+pub fn absoloute_error<T: RealField, const N: usize>(
+    actual: &SVector<T, N>,
+    predicted: &SVector<T, N>,
+) -> (T, SVector<T, N>) {
+    let delta = predicted - actual;
+    // the gradient is undefined at 0, so use 0 rather than dividing by it
+    let direction = delta
+        .try_normalize(T::zero())
+        .unwrap_or_else(SVector::zeros);
+    (delta.norm(), direction)
+}
+
+/// How the losses of every sample are combined
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Reduction {
+    /// The mean loss, and the mean gradient is used to train
+    #[default]
+    Mean,
+    /// The total loss, and the total gradient is used to train
+    Sum,
+    /// The loss of each sample, and the total gradient is used to train
+    None,
+}
+
+#[cfg(feature = "std-train")]
+impl Reduction {
+    /// Combines the loss of each sample. There is no mean of no losses, so [Reduction::Mean]
+    /// returns [TrainError::EmptyDataset] when losses is empty.
+    pub fn reduce<T: RealField + Copy>(self, losses: Vec<T>) -> Result<ReducedLoss<T>, TrainError> {
+        Ok(match self {
+            Reduction::Mean if losses.is_empty() => return Err(TrainError::EmptyDataset),
+            Reduction::Mean => {
+                let count: T = nalgebra::convert(losses.len() as f64);
+                ReducedLoss::Scalar(losses.into_iter().fold(T::zero(), |a, b| a + b) / count)
+            }
+            Reduction::Sum => ReducedLoss::Scalar(losses.into_iter().fold(T::zero(), |a, b| a + b)),
+            Reduction::None => ReducedLoss::PerSample(losses),
+        })
+    }
+}
+
+/// The losses of a set of samples, combined by a [Reduction]
+#[cfg(feature = "std-train")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReducedLoss<T> {
+    /// The mean or total loss
+    Scalar(T),
+    /// The loss of each sample, in order
+    PerSample(Vec<T>),
+}
+
+/// A loss which is the sum of a loss calculated for each output independently, allowing outputs to be weighted by
+/// [OutputWeighted]
+pub trait ElementwiseLoss<T: RealField> {
+    /// Returns the loss of one output and its deriviative with respect to predicted
+    fn element_loss(&self, actual: T, predicted: T) -> (T, T);
+}
+
+/// The Squared Error loss, computed one output at a time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SquaredError;
+
+impl<T: RealField + Copy> ElementwiseLoss<T> for SquaredError {
+    fn element_loss(&self, actual: T, predicted: T) -> (T, T) {
+        let delta = predicted - actual;
+        (delta * delta, delta + delta)
+    }
+}
+
+/// The sum of the absolute differences of each output, unlike [absoloute_error] which is the length of the difference
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AbsoluteError;
+
+impl<T: RealField + Copy> ElementwiseLoss<T> for AbsoluteError {
+    fn element_loss(&self, actual: T, predicted: T) -> (T, T) {
+        let delta = predicted - actual;
+        (delta.abs(), sign(delta))
+    }
+}
+
+/// The Huber loss, which is squared for differences smaller than delta and absolute for larger ones, making it less
+/// sensitive to outliers than [SquaredError]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Huber<T> {
+    /// The difference at which the loss changes from squared to absolute
+    pub delta: T,
+}
+
+impl<T: RealField + Copy> ElementwiseLoss<T> for Huber<T> {
+    fn element_loss(&self, actual: T, predicted: T) -> (T, T) {
+        let delta = predicted - actual;
+        let half: T = nalgebra::convert(0.5);
+
+        if delta.abs() <= self.delta {
+            (half * delta * delta, delta)
+        } else {
+            (
+                self.delta * (delta.abs() - half * self.delta),
+                self.delta * sign(delta),
+            )
+        }
+    }
+}
+
+/// Weights each output of an [ElementwiseLoss], for when some outputs matter more than others
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputWeighted<T: RealField, const N: usize, L> {
+    /// The loss of each output
+    pub loss: L,
+    /// What each output's loss is multiplied by
+    pub weights: SVector<T, N>,
+}
+
+impl<T: RealField + Copy, const N: usize, L: ElementwiseLoss<T>> Loss<T, N>
+    for OutputWeighted<T, N, L>
+{
+    fn loss(&self, actual: &SVector<T, N>, predicted: &SVector<T, N>) -> (T, SVector<T, N>) {
+        elementwise(&self.loss, actual, predicted, &self.weights)
+    }
+}
+
+/// Implements Loss for ElementwiseLosses, weighting every output equally
+macro_rules! impl_elementwise_loss {
+    ($loss:ty) => {
+        impl<T: RealField + Copy, const N: usize> Loss<T, N> for $loss {
+            fn loss(
+                &self,
+                actual: &SVector<T, N>,
+                predicted: &SVector<T, N>,
+            ) -> (T, SVector<T, N>) {
+                elementwise(self, actual, predicted, &SVector::repeat(T::one()))
+            }
+        }
+    };
+}
+
+impl_elementwise_loss!(SquaredError);
+impl_elementwise_loss!(AbsoluteError);
+impl_elementwise_loss!(Huber<T>);
+
+/// Sums the weighted loss of each output
+fn elementwise<T: RealField + Copy, const N: usize>(
+    loss: &impl ElementwiseLoss<T>,
+    actual: &SVector<T, N>,
+    predicted: &SVector<T, N>,
+    weights: &SVector<T, N>,
+) -> (T, SVector<T, N>) {
+    let mut total = T::zero();
+    let mut gradient = SVector::zeros();
+
+    for i in 0..N {
+        let (value, deriviative) = loss.element_loss(actual[i], predicted[i]);
+        total += weights[i] * value;
+        gradient[i] = weights[i] * deriviative;
+    }
+
+    (total, gradient)
+}
+
+/// Returns 1, -1 or 0 depending on the sign of x, as the deriviative of |x|
+pub(crate) fn sign<T: RealField>(x: T) -> T {
+    if x > T::zero() {
+        T::one()
+    } else if x < T::zero() {
+        -T::one()
+    } else {
+        T::zero()
+    }
+}
+
+/// Tests
+mod test {
+    #[test]
+    fn elementwise_loss_test() {
+        use super::{squared_error, Huber, Loss, OutputWeighted, SquaredError};
+        use nalgebra::Vector2;
+
+        let actual = Vector2::new(1f64, 2.);
+        let predicted = Vector2::new(2f64, -1.);
+
+        // the struct and the function should agree
+        assert_eq!(
+            SquaredError.loss(&actual, &predicted),
+            squared_error.loss(&actual, &predicted)
+        );
+
+        let (loss, gradient) = Huber { delta: 1. }.loss(&actual, &predicted);
+        assert_eq!(loss, 0.5 + 2.5);
+        assert_eq!(gradient, Vector2::new(1., -1.));
+
+        let weighted = OutputWeighted {
+            loss: SquaredError,
+            weights: Vector2::new(2., 0.),
+        };
+        let (loss, gradient) = weighted.loss(&actual, &predicted);
+        assert_eq!(loss, 2.);
+        assert_eq!(gradient, Vector2::new(4., 0.));
+    }
+
+    #[test]
+    #[cfg(feature = "std-train")]
+    fn reduction_test() {
+        extern crate std;
+
+        use super::{ReducedLoss, Reduction};
+        use crate::TrainError;
+        use std::vec;
+
+        assert_eq!(
+            Reduction::Mean.reduce(vec![1f64, 2., 6.]),
+            Ok(ReducedLoss::Scalar(3.))
+        );
+        assert_eq!(
+            Reduction::Mean.reduce::<f64>(vec![]),
+            Err(TrainError::EmptyDataset)
+        );
+        assert_eq!(
+            Reduction::Sum.reduce::<f64>(vec![]),
+            Ok(ReducedLoss::Scalar(0.))
+        );
+        assert_eq!(
+            Reduction::None.reduce::<f64>(vec![]),
+            Ok(ReducedLoss::PerSample(vec![]))
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::valueset::ValueSet;
+use core::f32;
+use nalgebra::RealField;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Represents an optimiser
+pub trait Optimiser<T: RealField + Copy, G> {
+    /// Transforms the gradient into the step to take. Optimisers with state should return a step
+    /// [ValueSet::with_sparsity_of] the gradient, so sparse gradients stay sparse.
+    fn transform(&mut self, gradient: &G) -> G;
+}
+
+/// The ADAM optimiser
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AdamOptimiser<T: RealField + Copy, G: ValueSet<T>> {
+    /// The momentum variable in the ADAM optimiser
+    momentum: G,
+    /// The velocity variable in the ADAM optimiser
+    velocity: G,
+
+    /// Learning rate(alpha) hyperparameter
+    pub learning_rate: T,
+    /// Momentum "mixer" (beta1) hyperparameter
+    pub momentum_mixer: T,
+    /// Velocity "mixer" (beta2) hyperparameter
+    pub velocity_mixer: T,
+
+    /// beta1 ^ t field to optimise sequential generation
+    accumulated_momentum: T,
+    /// beta2 ^ t field to optimise sequential generation
+    accumulated_velocity: T,
+}
+
+impl<T: RealField + Copy, G: ValueSet<T> + Default> AdamOptimiser<T, G> {
+    /// Creates a new AdamOptimiser with the given hyperparameters
+    pub fn new(learning_rate: T, momentum_mixer: T, velocity_mixer: T) -> Self {
+        Self {
+            momentum: G::default(),
+            velocity: G::default(),
+            learning_rate,
+            momentum_mixer,
+            velocity_mixer,
+            accumulated_momentum: momentum_mixer,
+            accumulated_velocity: velocity_mixer,
+        }
+    }
+}
+
+impl<T: RealField + Copy + From<f32>, G: ValueSet<T> + Default> Default for AdamOptimiser<T, G> {
+    fn default() -> Self {
+        Self::new(0.001.into(), 0.9.into(), 0.999.into())
+    }
+}
+
+impl<T: RealField + Copy, G: ValueSet<T>> Optimiser<T, G> for AdamOptimiser<T, G> {
+    fn transform(&mut self, gradient: &G) -> G {
+        // Calculate M[t+1] and V[t+1] respectively:
+        self.momentum = self.momentum.binary_operation(gradient, |&mom, &gra| {
+            mom * self.momentum_mixer + gra * (T::one() - self.momentum_mixer)
+        });
+
+        self.velocity = self.velocity.binary_operation(gradient, |&vel, &gra| {
+            vel * self.velocity_mixer + gra.powi(2) * (T::one() - self.velocity_mixer)
+        });
+
+        // Calculate M^ [t+1] and V^ [t+1] respectively:
+        let corrected_momentum = self
+            .momentum
+            .unary_operation(|&mom| mom / (T::one() - self.accumulated_momentum));
+        let corrected_velocity = self
+            .velocity
+            .unary_operation(|&vel| vel / (T::one() - self.accumulated_velocity));
+
+        // Update accumulated values
+        self.accumulated_momentum *= self.momentum_mixer;
+        self.accumulated_velocity *= self.velocity_mixer;
+
+        corrected_momentum
+            .binary_operation(&corrected_velocity, |&mom, &vel| {
+                -self.learning_rate * mom / (vel.sqrt() + T::one()) // FIXME: Use T::min_value() instead of T::one() when/if this pr gets merged and shipped with nalgebra: https://github.com/dimforge/simba/pull/65
+            })
+            .with_sparsity_of(gradient)
+    }
+}
+
+/// Multiplies the steps taken by another optimiser by a learning rate multiplier for each part of
+/// the gradient(e.g. each layer of a `network!`, and each network in a [crate::ChainedNetwork] in
+/// order), so a pretrained part can be fine-tuned slowly, or frozen with a multiplier of 0.
+/// ## Example
+/// ```rust
+///     use neural_thingamajigy::{network, TrainableNetwork, ValueSet};
+///     use neural_thingamajigy::optimiser::{AdamOptimiser, LearningRateMultipliers};
+///
+///     network!(pub FineTuned, f32, 4, 8, 8, 1);
+///     type Gradient = <FineTuned as TrainableNetwork<f32, 4, 1>>::Gradient;
+///     assert_eq!(Gradient::parts(), 3);
+///
+///     // the first layer is frozen, and the second learns at a tenth of the rate of the last
+///     let optimiser =
+///         LearningRateMultipliers::new(AdamOptimiser::<f32, Gradient>::default(), [0., 0.1, 1.]);
+/// ```
+/// # Panics
+/// [Optimiser::transform] panics if `PARTS` isn't the number of parts in the gradient
+pub struct LearningRateMultipliers<O, T, const PARTS: usize> {
+    /// The optimiser whose steps are multiplied
+    pub optimiser: O,
+    /// The learning rate multiplier of each part
+    pub multipliers: [T; PARTS],
+}
+
+impl<O, T, const PARTS: usize> LearningRateMultipliers<O, T, PARTS> {
+    /// Multiplies the steps of optimiser by multipliers
+    pub fn new(optimiser: O, multipliers: [T; PARTS]) -> Self {
+        Self {
+            optimiser,
+            multipliers,
+        }
+    }
+}
+
+impl<T: RealField + Copy, G: ValueSet<T>, O: Optimiser<T, G>, const PARTS: usize> Optimiser<T, G>
+    for LearningRateMultipliers<O, T, PARTS>
+{
+    fn transform(&mut self, gradient: &G) -> G {
+        assert_eq!(
+            G::parts(),
+            PARTS,
+            "there must be a learning rate multiplier for each part of the gradient"
+        );
+
+        self.optimiser
+            .transform(gradient)
+            .indexed_operation(|part, &step| step * self.multipliers[part])
+    }
+}
@@ -1,10 +1,14 @@
 use core::marker::PhantomData;
 
-use nalgebra::{RealField, SVector};
+use nalgebra::{RealField, SMatrix, SVector};
 
-use crate::{activators::Activator, Network};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{activators::Activator, LayerInfo, Network, NetworkInfo};
 
 /// 2 networks that have been chained together
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChainedNetwork<
     T: RealField + Copy,
     const INPUTS: usize,
@@ -19,6 +23,7 @@ pub struct ChainedNetwork<
     pub second: B,
 
     /// PhantomData to gaslight the compiler
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub _marker: PhantomData<T>,
 }
 
@@ -39,6 +44,15 @@ impl<
         self.second
             .evaluate(self.first.evaluate(inputs, activator), activator)
     }
+
+    fn evaluate_batch<const BATCH: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, BATCH>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, OUTPUTS, BATCH> {
+        self.second
+            .evaluate_batch(self.first.evaluate_batch(inputs, activator), activator)
+    }
 }
 
 impl<
@@ -60,6 +74,20 @@ impl<
     }
 }
 
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const MIDDLE: usize,
+        const OUTPUTS: usize,
+        A: Network<T, INPUTS, MIDDLE> + NetworkInfo<INPUTS, MIDDLE>,
+        B: Network<T, MIDDLE, OUTPUTS> + NetworkInfo<MIDDLE, OUTPUTS>,
+    > NetworkInfo<INPUTS, OUTPUTS> for ChainedNetwork<T, INPUTS, MIDDLE, OUTPUTS, A, B>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        self.first.layer_info().chain(self.second.layer_info())
+    }
+}
+
 /// A network that can be chained with others
 pub trait ChainableNetwork<T: RealField + Copy, const INPUTS: usize, const MIDDLE: usize>
 where
@@ -107,9 +135,9 @@ impl<
 {
 }
 
-#[cfg(feature = "train")]
-use super::TrainableNetwork;
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
+use super::{BatchTrainableNetwork, TrainableNetwork};
+#[cfg(feature = "backprop")]
 impl<
         T: RealField + Copy,
         const INPUTS: usize,
@@ -150,8 +178,78 @@ impl<
         ((first_gradient, second_gradient), input_loss_gradient)
     }
 
+    fn get_input_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, OUTPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        let middle_loss_gradient =
+            self.second
+                .get_input_gradient(&layer_inputs.1, output_loss_gradients, activator);
+
+        self.first
+            .get_input_gradient(&layer_inputs.0, middle_loss_gradient, activator)
+    }
+
     fn apply_nudge(&mut self, nudge: Self::Gradient) {
         self.first.apply_nudge(nudge.0);
         self.second.apply_nudge(nudge.1);
     }
 }
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const MIDDLE: usize,
+        const OUTPUTS: usize,
+        A: Network<T, INPUTS, MIDDLE> + BatchTrainableNetwork<T, INPUTS, MIDDLE>,
+        B: Network<T, MIDDLE, OUTPUTS> + BatchTrainableNetwork<T, MIDDLE, OUTPUTS>,
+    > BatchTrainableNetwork<T, INPUTS, OUTPUTS>
+    for ChainedNetwork<T, INPUTS, MIDDLE, OUTPUTS, A, B>
+{
+    type BatchLayerInputs<const BATCH: usize> =
+        (A::BatchLayerInputs<BATCH>, B::BatchLayerInputs<BATCH>);
+
+    fn evaluate_training_batch<const BATCH: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, BATCH>,
+        activator: &impl Activator<T>,
+    ) -> (SMatrix<T, OUTPUTS, BATCH>, Self::BatchLayerInputs<BATCH>) {
+        let (middle, first_data) = self.first.evaluate_training_batch(inputs, activator);
+        let (outputs, second_data) = self.second.evaluate_training_batch(middle, activator);
+
+        (outputs, (first_data, second_data))
+    }
+
+    fn get_gradient_batch<const BATCH: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<BATCH>,
+        output_loss_gradients: SMatrix<T, OUTPUTS, BATCH>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SMatrix<T, INPUTS, BATCH>) {
+        let (second_gradient, middle_loss_gradient) =
+            self.second
+                .get_gradient_batch(&layer_inputs.1, output_loss_gradients, activator);
+        let (first_gradient, input_loss_gradient) =
+            self.first
+                .get_gradient_batch(&layer_inputs.0, middle_loss_gradient, activator);
+
+        ((first_gradient, second_gradient), input_loss_gradient)
+    }
+
+    fn get_input_gradient_batch<const BATCH: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<BATCH>,
+        output_loss_gradients: SMatrix<T, OUTPUTS, BATCH>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, INPUTS, BATCH> {
+        let middle_loss_gradient =
+            self.second
+                .get_input_gradient_batch(&layer_inputs.1, output_loss_gradients, activator);
+
+        self.first
+            .get_input_gradient_batch(&layer_inputs.0, middle_loss_gradient, activator)
+    }
+}
@@ -0,0 +1,102 @@
+extern crate std;
+
+use core::fmt::{self, Display};
+use nalgebra::SVector;
+use std::{error::Error, io, string::String, vec::Vec};
+
+/// Loads datasets from CSV files
+pub mod csv;
+/// Loads datasets from NumPy `.npy` and `.npz` files
+pub mod npy;
+
+pub use {csv::*, npy::*};
+
+/// A set of `(input, expected output)` pairs, as accepted by [crate::train]
+pub type Dataset<T, const INPUTS: usize, const OUTPUTS: usize> =
+    Vec<(SVector<T, INPUTS>, SVector<T, OUTPUTS>)>;
+
+/// An error produced while loading a dataset
+#[derive(Debug)]
+pub enum DataError {
+    /// The underlying reader failed
+    Io(io::Error),
+    /// A value couldn't be parsed as a number
+    Parse {
+        /// The line(1 based) the value is on
+        line: usize,
+        /// The column(0 based) the value is in
+        column: usize,
+        /// The text that failed to parse
+        value: String,
+    },
+    /// The data doesn't have the width the network's `INPUTS`/`OUTPUTS` generics require
+    WidthMismatch {
+        /// What was being measured, e.g. `"features"` or `"row 7"`
+        context: String,
+        /// The width required
+        expected: usize,
+        /// The width found in the file
+        found: usize,
+    },
+    /// A column was selected by name, but no header has that name, or by an index past the last
+    /// column
+    MissingColumn(String),
+    /// A label doesn't belong to any of the one-hot classes
+    UnknownLabel {
+        /// The line(1 based) the label is on
+        line: usize,
+        /// The offending label
+        label: String,
+    },
+    /// The file is malformed
+    Format(String),
+    /// The file is valid, but uses something this loader doesn't support
+    Unsupported(String),
+}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Io(e) => write!(f, "io error: {}", e),
+            DataError::Parse {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "couldn't parse {:?} as a number (line {}, column {})",
+                value, line, column
+            ),
+            DataError::WidthMismatch {
+                context,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} has width {}, but the network expects {}",
+                context, found, expected
+            ),
+            DataError::MissingColumn(name) => write!(f, "no column {:?}", name),
+            DataError::UnknownLabel { line, label } => {
+                write!(f, "unknown label {:?} on line {}", label, line)
+            }
+            DataError::Format(message) => write!(f, "malformed file: {}", message),
+            DataError::Unsupported(message) => write!(f, "unsupported: {}", message),
+        }
+    }
+}
+
+impl Error for DataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DataError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DataError {
+    fn from(value: io::Error) -> Self {
+        DataError::Io(value)
+    }
+}
@@ -0,0 +1,299 @@
+extern crate std;
+
+use super::{DataError, Dataset};
+use nalgebra::{RealField, SVector};
+use std::{
+    borrow::ToOwned,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Selects a column of a CSV file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The column at this (0 based) position
+    Index(usize),
+    /// The column with this header, requires [CsvOptions::has_header]
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(value: usize) -> Self {
+        Column::Index(value)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(value: &str) -> Self {
+        Column::Name(value.to_owned())
+    }
+}
+
+/// How the expected outputs are read from a CSV file
+#[derive(Clone, Debug)]
+pub enum Targets {
+    /// Each column holds one numeric output, in order
+    Columns(Vec<Column>),
+    /// A single column holds a label, which is one-hot encoded
+    OneHot {
+        /// The column holding the label
+        column: Column,
+        /// The labels, in output order. If empty, every distinct label in the file is used, sorted.
+        classes: Vec<String>,
+    },
+}
+
+/// Describes the layout of a CSV file
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    /// The character seperating fields
+    pub delimiter: char,
+    /// Whether the first line is a header
+    pub has_header: bool,
+    /// The columns used as inputs, in order. If None, every column not used by `targets` is used.
+    pub features: Option<Vec<Column>>,
+    /// The columns used as expected outputs
+    pub targets: Targets,
+}
+
+impl CsvOptions {
+    /// Creates options for a comma delimited file with a header, using every non-target column as a feature
+    pub fn new(targets: Targets) -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+            features: None,
+            targets,
+        }
+    }
+}
+
+/// Reads `(input, expected output)` pairs from CSV data
+pub fn read_csv<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    reader: impl BufRead,
+    options: &CsvOptions,
+) -> Result<Dataset<T, INPUTS, OUTPUTS>, DataError> {
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.map(|l| (i + 1, l)))
+        .filter(|line| !matches!(line, Ok((_, l)) if l.trim().is_empty()));
+
+    let header = if options.has_header {
+        match lines.next() {
+            Some(line) => Some(split_line(&line?.1, options.delimiter)),
+            None => return Ok(Vec::new()),
+        }
+    } else {
+        None
+    };
+
+    let mut rows = lines.peekable();
+    let width = match (&header, rows.peek()) {
+        (Some(h), _) => h.len(),
+        (None, Some(Ok((_, first)))) => split_line(first, options.delimiter).len(),
+        (None, Some(Err(_))) => 0,
+        (None, None) => return Ok(Vec::new()),
+    };
+
+    let resolve = |column: &Column| -> Result<usize, DataError> {
+        match column {
+            Column::Index(i) if *i < width => Ok(*i),
+            Column::Index(i) => Err(DataError::MissingColumn(i.to_string())),
+            Column::Name(name) => header
+                .as_ref()
+                .and_then(|h| h.iter().position(|c| c.trim() == name))
+                .ok_or_else(|| DataError::MissingColumn(name.clone())),
+        }
+    };
+
+    let (target_columns, label_column) = match &options.targets {
+        Targets::Columns(columns) => (
+            columns.iter().map(resolve).collect::<Result<Vec<_>, _>>()?,
+            None,
+        ),
+        Targets::OneHot { column, .. } => (Vec::new(), Some(resolve(column)?)),
+    };
+
+    let feature_columns = match &options.features {
+        Some(columns) => columns.iter().map(resolve).collect::<Result<Vec<_>, _>>()?,
+        None => (0..width)
+            .filter(|i| !target_columns.contains(i) && label_column != Some(*i))
+            .collect(),
+    };
+
+    check_width("features", INPUTS, feature_columns.len())?;
+    if label_column.is_none() {
+        check_width("targets", OUTPUTS, target_columns.len())?;
+    }
+
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut labels = Vec::new();
+
+    for row in rows {
+        let (line, text) = row?;
+        let fields = split_line(&text, options.delimiter);
+        check_width(&std::format!("line {}", line), width, fields.len())?;
+
+        let parse = |column: usize| -> Result<T, DataError> {
+            let value = fields[column].trim();
+            value
+                .parse::<f64>()
+                .map(nalgebra::convert)
+                .map_err(|_| DataError::Parse {
+                    line,
+                    column,
+                    value: value.to_owned(),
+                })
+        };
+
+        let mut input = SVector::<T, INPUTS>::zeros();
+        for (i, &column) in feature_columns.iter().enumerate() {
+            input[i] = parse(column)?;
+        }
+        inputs.push(input);
+
+        match label_column {
+            Some(column) => labels.push((line, fields[column].trim().to_owned())),
+            None => {
+                let mut output = SVector::<T, OUTPUTS>::zeros();
+                for (i, &column) in target_columns.iter().enumerate() {
+                    output[i] = parse(column)?;
+                }
+                outputs.push(output);
+            }
+        }
+    }
+
+    if let Targets::OneHot { classes, .. } = &options.targets {
+        let classes = if classes.is_empty() {
+            let mut found: Vec<String> = labels.iter().map(|(_, l)| l.clone()).collect();
+            found.sort();
+            found.dedup();
+            found
+        } else {
+            classes.clone()
+        };
+        check_width("one-hot classes", OUTPUTS, classes.len())?;
+
+        for (line, label) in labels {
+            let class = classes
+                .iter()
+                .position(|c| *c == label)
+                .ok_or(DataError::UnknownLabel { line, label })?;
+
+            let mut output = SVector::<T, OUTPUTS>::zeros();
+            output[class] = T::one();
+            outputs.push(output);
+        }
+    }
+
+    Ok(inputs.into_iter().zip(outputs).collect())
+}
+
+/// Loads `(input, expected output)` pairs from the CSV file at path
+pub fn load_csv<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    path: impl AsRef<Path>,
+    options: &CsvOptions,
+) -> Result<Dataset<T, INPUTS, OUTPUTS>, DataError> {
+    read_csv(BufReader::new(File::open(path)?), options)
+}
+
+/// Returns a WidthMismatch error if found isn't expected
+fn check_width(context: &str, expected: usize, found: usize) -> Result<(), DataError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(DataError::WidthMismatch {
+            context: context.to_string(),
+            expected,
+            found,
+        })
+    }
+}
+
+/// Splits a line into fields, handling double-quoted fields and `""` escapes
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(core::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+/// Tests
+mod test {
+    extern crate std;
+
+    #[test]
+    fn csv_test() {
+        use super::{read_csv, CsvOptions, Targets};
+        use crate::data::DataError;
+        use nalgebra::{Vector2, Vector3};
+        use std::vec;
+
+        let file = "a,label,b\n1,cat,2\n\n\"3\",dog,4.5\n5,\"bird\",-6\n";
+        let options = CsvOptions::new(Targets::OneHot {
+            column: "label".into(),
+            classes: vec![],
+        });
+
+        let data = read_csv::<f32, 2, 3>(file.as_bytes(), &options).unwrap();
+        assert_eq!(
+            data,
+            vec![
+                (Vector2::new(1., 2.), Vector3::new(0., 1., 0.)),
+                (Vector2::new(3., 4.5), Vector3::new(0., 0., 1.)),
+                (Vector2::new(5., -6.), Vector3::new(1., 0., 0.)),
+            ]
+        );
+
+        // Only 3 classes exist, so this must fail before training starts
+        assert!(matches!(
+            read_csv::<f32, 2, 4>(file.as_bytes(), &options),
+            Err(DataError::WidthMismatch {
+                expected: 4,
+                found: 3,
+                ..
+            })
+        ));
+
+        let options = CsvOptions {
+            delimiter: ';',
+            has_header: false,
+            features: Some(vec![2.into()]),
+            targets: Targets::Columns(vec![0.into(), 1.into()]),
+        };
+        let data = read_csv::<f64, 1, 2>("1;2;3\n4;5;6".as_bytes(), &options).unwrap();
+        assert_eq!(data[1].0.x, 6.);
+        assert_eq!(data[1].1, Vector2::new(4., 5.));
+
+        // There's no fourth column to read a feature from
+        let options = CsvOptions {
+            features: Some(vec![3.into()]),
+            ..options
+        };
+        assert!(matches!(
+            read_csv::<f64, 1, 2>("1;2;3\n4;5;6".as_bytes(), &options),
+            Err(DataError::MissingColumn(column)) if column == "3"
+        ));
+    }
+}
@@ -0,0 +1,463 @@
+extern crate std;
+
+use super::{DataError, Dataset};
+use nalgebra::{RealField, SVector};
+use std::{
+    borrow::ToOwned,
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// An array read from a `.npy` file
+#[derive(Clone, Debug, PartialEq)]
+pub struct NpyArray {
+    /// The length of each dimension
+    pub shape: Vec<usize>,
+    /// Every value in row-major(C) order, regardless of the order in the file
+    pub data: Vec<f64>,
+}
+
+/// How the target array is turned into expected outputs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetEncoding {
+    /// Each row holds the outputs
+    Values,
+    /// Each row holds a single integer class label, which is one-hot encoded
+    OneHot,
+}
+
+/// Reads an array in the `.npy` format
+pub fn read_npy(mut reader: impl Read) -> Result<NpyArray, DataError> {
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != b"\x93NUMPY" {
+        return Err(DataError::Format("missing .npy magic string".to_owned()));
+    }
+
+    let header_length = match preamble[6] {
+        1 => {
+            let mut length = [0u8; 2];
+            reader.read_exact(&mut length)?;
+            u16::from_le_bytes(length) as usize
+        }
+        2 | 3 => {
+            let mut length = [0u8; 4];
+            reader.read_exact(&mut length)?;
+            u32::from_le_bytes(length) as usize
+        }
+        version => {
+            return Err(DataError::Unsupported(std::format!(
+                ".npy version {}",
+                version
+            )))
+        }
+    };
+
+    let header = read_length(&mut reader, header_length as u64)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr = header_value(&header, "descr")?;
+    let descr = descr
+        .get(1..)
+        .and_then(|d| d.split(['\'', '"']).next())
+        .unwrap_or_default();
+    let fortran_order = header_value(&header, "fortran_order")?.starts_with("True");
+    let shape = header_value(&header, "shape")?;
+    let shape = shape[..shape.find(')').unwrap_or(shape.len())]
+        .trim_start_matches('(')
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<usize>()
+                .map_err(|_| DataError::Format(std::format!("bad dimension {:?}", s)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (endianness, kind) = descr.split_at(descr.len().min(1));
+    let big_endian = match endianness {
+        "<" | "|" | "=" => false,
+        ">" => true,
+        _ => return Err(DataError::Unsupported(std::format!("dtype {:?}", descr))),
+    };
+    let element: fn(&[u8]) -> f64 = match kind {
+        "b1" | "u1" => |b| b[0] as f64,
+        "i1" => |b| b[0] as i8 as f64,
+        "i2" => |b| i16::from_le_bytes([b[0], b[1]]) as f64,
+        "u2" => |b| u16::from_le_bytes([b[0], b[1]]) as f64,
+        "i4" => |b| i32::from_le_bytes(b.try_into().unwrap()) as f64,
+        "u4" => |b| u32::from_le_bytes(b.try_into().unwrap()) as f64,
+        "i8" => |b| i64::from_le_bytes(b.try_into().unwrap()) as f64,
+        "u8" => |b| u64::from_le_bytes(b.try_into().unwrap()) as f64,
+        "f4" => |b| f32::from_le_bytes(b.try_into().unwrap()) as f64,
+        "f8" => |b| f64::from_le_bytes(b.try_into().unwrap()),
+        _ => return Err(DataError::Unsupported(std::format!("dtype {:?}", descr))),
+    };
+    let size: usize = kind[1..].parse().unwrap(); // the kinds above all end with their size
+
+    let count = checked_product(&shape)?;
+    let length = count
+        .checked_mul(size)
+        .ok_or_else(|| DataError::Format(std::format!("shape {:?} is too large", shape)))?;
+    let mut bytes = read_length(&mut reader, length as u64)?;
+
+    let data: Vec<f64> = bytes
+        .chunks_exact_mut(size)
+        .map(|chunk| {
+            if big_endian {
+                chunk.reverse();
+            }
+            element(chunk)
+        })
+        .collect();
+
+    let data = if fortran_order && shape.len() > 1 {
+        // walk the column-major data, writing each value to its row-major position
+        let mut c_order = vec![0f64; count];
+        let mut index = vec![0usize; shape.len()];
+        for &value in &data {
+            let offset = index
+                .iter()
+                .zip(&shape)
+                .fold(0, |offset, (&i, &dim)| offset * dim + i);
+            c_order[offset] = value;
+
+            // advance the index with the first dimension changing fastest
+            for (i, &dim) in index.iter_mut().zip(&shape) {
+                *i += 1;
+                if *i < dim {
+                    break;
+                }
+                *i = 0;
+            }
+        }
+        c_order
+    } else {
+        data
+    };
+
+    Ok(NpyArray { shape, data })
+}
+
+/// Loads the array in the `.npy` file at path
+pub fn load_npy(path: impl AsRef<Path>) -> Result<NpyArray, DataError> {
+    read_npy(BufReader::new(File::open(path)?))
+}
+
+/// Reads every array in an `.npz` archive, returning `(name, array)` pairs. Only uncompressed
+/// archives (as written by `np.savez`, not `np.savez_compressed`) are supported.
+pub fn read_npz(mut reader: impl Read + Seek) -> Result<Vec<(String, NpyArray)>, DataError> {
+    let length = reader.seek(SeekFrom::End(0))?;
+
+    // The end of central directory record is at least 22 bytes, followed by a comment of up to 65535 bytes
+    let tail_length = length.min(22 + u16::MAX as u64);
+    reader.seek(SeekFrom::End(-(tail_length as i64)))?;
+    let tail = read_length(&mut reader, tail_length)?;
+
+    let end = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| tail[i..i + 4] == [0x50, 0x4b, 0x05, 0x06])
+        .ok_or_else(|| DataError::Format("no zip end of central directory record".to_owned()))?;
+    let entries = u16_at(&tail, end + 10) as usize;
+    let directory_offset = u32_at(&tail, end + 16) as u64;
+
+    let mut directory = Vec::new();
+    reader.seek(SeekFrom::Start(directory_offset))?;
+    for _ in 0..entries {
+        let mut fixed = [0u8; 46];
+        reader.read_exact(&mut fixed)?;
+        if fixed[..4] != [0x50, 0x4b, 0x01, 0x02] {
+            return Err(DataError::Format(
+                "bad zip central directory entry".to_owned(),
+            ));
+        }
+
+        let compression = u16_at(&fixed, 10);
+        let mut size = u32_at(&fixed, 24) as u64;
+        let mut offset = u32_at(&fixed, 42) as u64;
+
+        let mut variable = vec![
+            0u8;
+            u16_at(&fixed, 28) as usize
+                + u16_at(&fixed, 30) as usize
+                + u16_at(&fixed, 32) as usize
+        ];
+        reader.read_exact(&mut variable)?;
+        let (name, extra) = variable.split_at(u16_at(&fixed, 28) as usize);
+
+        // Zip64 stores sizes and offsets that don't fit in the record in an extra field
+        let mut extra = &extra[..u16_at(&fixed, 30) as usize];
+        while extra.len() >= 4 {
+            let (id, field_length) = (u16_at(extra, 0), u16_at(extra, 2) as usize);
+            let field = &extra[4..(4 + field_length).min(extra.len())];
+            if id == 1 {
+                let mut values = field.chunks_exact(8).map(|v| u64_at(v, 0));
+                if u32_at(&fixed, 24) == u32::MAX {
+                    size = values.next().unwrap_or(size);
+                }
+                if u32_at(&fixed, 20) == u32::MAX {
+                    values.next(); // compressed size
+                }
+                if u32_at(&fixed, 42) == u32::MAX {
+                    offset = values.next().unwrap_or(offset);
+                }
+            }
+            extra = &extra[(4 + field_length).min(extra.len())..];
+        }
+
+        let name = String::from_utf8_lossy(name).to_string();
+        if compression != 0 {
+            return Err(DataError::Unsupported(std::format!(
+                "{} is compressed, save the archive with np.savez instead of np.savez_compressed",
+                name
+            )));
+        }
+        directory.push((name, size, offset));
+    }
+
+    directory
+        .into_iter()
+        .map(|(name, size, offset)| {
+            let mut local = [0u8; 30];
+            reader.seek(SeekFrom::Start(offset))?;
+            reader.read_exact(&mut local)?;
+            let skip = u16_at(&local, 26) as i64 + u16_at(&local, 28) as i64;
+            reader.seek(SeekFrom::Current(skip))?;
+
+            let array = read_npy((&mut reader).take(size))?;
+            let name = name.strip_suffix(".npy").unwrap_or(&name).to_owned();
+            Ok((name, array))
+        })
+        .collect()
+}
+
+/// Loads every array in the `.npz` archive at path, see [read_npz]
+pub fn load_npz(path: impl AsRef<Path>) -> Result<Vec<(String, NpyArray)>, DataError> {
+    read_npz(BufReader::new(File::open(path)?))
+}
+
+/// Pairs up rows of a feature and a target array into `(input, expected output)` pairs.
+/// `features` must have the shape `(samples, INPUTS)`, `targets` must be `(samples, OUTPUTS)`
+/// or, when one-hot encoded, `(samples,)`. Single width dimensions may be omitted.
+pub fn npy_pairs<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    features: &NpyArray,
+    targets: &NpyArray,
+    encoding: TargetEncoding,
+) -> Result<Dataset<T, INPUTS, OUTPUTS>, DataError> {
+    let target_width = match encoding {
+        TargetEncoding::Values => OUTPUTS,
+        TargetEncoding::OneHot => 1,
+    };
+    let samples = rows(features, "features", INPUTS)?;
+    let target_samples = rows(targets, "targets", target_width)?;
+    if samples != target_samples {
+        return Err(DataError::Format(std::format!(
+            "{} feature rows but {} target rows",
+            samples,
+            target_samples
+        )));
+    }
+
+    features
+        .data
+        .chunks_exact(INPUTS.max(1))
+        .zip(targets.data.chunks_exact(target_width.max(1)))
+        .enumerate()
+        .map(|(i, (x, y))| {
+            let input = SVector::from_iterator(x.iter().map(|&v| nalgebra::convert(v)));
+            let output = match encoding {
+                TargetEncoding::Values => {
+                    SVector::from_iterator(y.iter().map(|&v| nalgebra::convert(v)))
+                }
+                TargetEncoding::OneHot => {
+                    let label = y[0];
+                    if label < 0. || label.fract() != 0. || label as usize >= OUTPUTS {
+                        return Err(DataError::UnknownLabel {
+                            line: i + 1,
+                            label: label.to_string(),
+                        });
+                    }
+                    let mut output = SVector::zeros();
+                    output[label as usize] = T::one();
+                    output
+                }
+            };
+            Ok((input, output))
+        })
+        .collect()
+}
+
+/// Checks that array is a stack of rows with the given width, returning how many rows there are
+fn rows(array: &NpyArray, context: &str, width: usize) -> Result<usize, DataError> {
+    let found = match array.shape.as_slice() {
+        [_] => 1,
+        [_, found] => *found,
+        _ => {
+            return Err(DataError::Unsupported(std::format!(
+                "{} must be 1 or 2 dimensional, but has shape {:?}",
+                context,
+                array.shape
+            )))
+        }
+    };
+
+    if found != width {
+        return Err(DataError::WidthMismatch {
+            context: context.to_owned(),
+            expected: width,
+            found,
+        });
+    }
+
+    Ok(array.shape[0])
+}
+
+/// Reads exactly length bytes, without trusting length enough to allocate it up front
+pub(crate) fn read_length(reader: impl Read, length: u64) -> Result<Vec<u8>, DataError> {
+    let mut bytes = Vec::new();
+    reader.take(length).read_to_end(&mut bytes)?;
+
+    if bytes.len() as u64 == length {
+        Ok(bytes)
+    } else {
+        Err(DataError::Io(std::io::ErrorKind::UnexpectedEof.into()))
+    }
+}
+
+/// The number of values in an array with shape, or a Format error if that overflows
+pub(crate) fn checked_product(shape: &[usize]) -> Result<usize, DataError> {
+    shape
+        .iter()
+        .try_fold(1usize, |count, &dim| count.checked_mul(dim))
+        .ok_or_else(|| DataError::Format(std::format!("shape {:?} is too large", shape)))
+}
+
+/// Finds the text following `'key':` in a `.npy` header
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, DataError> {
+    let quoted = std::format!("'{}'", key);
+    header
+        .find(&quoted)
+        .map(|i| header[i + quoted.len()..].trim_start_matches([':', ' ']))
+        .ok_or_else(|| DataError::Format(std::format!(".npy header is missing {}", key)))
+}
+
+/// Reads a little endian u16 at offset
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Reads a little endian u32 at offset
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+/// Reads a little endian u64 at offset
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// Tests
+mod test {
+    extern crate std;
+
+    /// Builds a `.npy` file with the given header dictionary and data
+    #[cfg(test)]
+    fn npy_file(header: &str, data: &[u8]) -> std::vec::Vec<u8> {
+        let mut file = b"\x93NUMPY\x01\x00".to_vec();
+        file.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        file.extend_from_slice(header.as_bytes());
+        file.extend_from_slice(data);
+        file
+    }
+
+    #[test]
+    fn npy_test() {
+        use super::{npy_pairs, read_npy, read_npz, TargetEncoding};
+        use crate::data::DataError;
+        use nalgebra::{Vector2, Vector3};
+        use std::io::Cursor;
+
+        let features: std::vec::Vec<u8> = [1f32, 2., 3., 4.]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        // Stored column-major, so the rows are (1, 3) and (2, 4)
+        let features = npy_file(
+            "{'descr': '<f4', 'fortran_order': True, 'shape': (2, 2), }\n",
+            &features,
+        );
+        let labels = npy_file(
+            "{'descr': '|u1', 'fortran_order': False, 'shape': (2,), }\n",
+            &[2, 0],
+        );
+
+        let features = read_npy(features.as_slice()).unwrap();
+        assert_eq!(features.data, [1., 3., 2., 4.]);
+
+        let labels = read_npy(labels.as_slice()).unwrap();
+        let data = npy_pairs::<f64, 2, 3>(&features, &labels, TargetEncoding::OneHot).unwrap();
+        assert_eq!(data[0], (Vector2::new(1., 3.), Vector3::new(0., 0., 1.)));
+        assert_eq!(data[1], (Vector2::new(2., 4.), Vector3::new(1., 0., 0.)));
+
+        assert!(matches!(
+            npy_pairs::<f64, 3, 3>(&features, &labels, TargetEncoding::OneHot),
+            Err(DataError::WidthMismatch {
+                expected: 3,
+                found: 2,
+                ..
+            })
+        ));
+
+        // A stored zip with a single entry, laid out as np.savez writes it
+        let entry = npy_file(
+            "{'descr': '>i2', 'fortran_order': False, 'shape': (1,), }\n",
+            &[0x01, 0x02],
+        );
+        let mut archive = std::vec::Vec::new();
+        archive.extend_from_slice(&[0x50, 0x4b, 0x03, 0x04]);
+        archive.extend_from_slice(&[0; 14]);
+        archive.extend_from_slice(&(entry.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&(entry.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&5u16.to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive.extend_from_slice(b"x.npy");
+        archive.extend_from_slice(&entry);
+
+        let directory = archive.len() as u32;
+        archive.extend_from_slice(&[0x50, 0x4b, 0x01, 0x02]);
+        archive.extend_from_slice(&[0; 16]);
+        archive.extend_from_slice(&(entry.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&(entry.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&5u16.to_le_bytes());
+        archive.extend_from_slice(&[0; 12]);
+        archive.extend_from_slice(&0u32.to_le_bytes());
+        archive.extend_from_slice(b"x.npy");
+        let directory_length = archive.len() as u32 - directory;
+
+        archive.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0, 1, 0, 1, 0]);
+        archive.extend_from_slice(&directory_length.to_le_bytes());
+        archive.extend_from_slice(&directory.to_le_bytes());
+        archive.extend_from_slice(&[0, 0]);
+
+        let arrays = read_npz(Cursor::new(archive)).unwrap();
+        assert_eq!(arrays.len(), 1);
+        assert_eq!(arrays[0].0, "x");
+        assert_eq!(arrays[0].1.data, [258.]);
+
+        // A header claiming more values than fit in memory(or a usize) is an error, not an abort
+        for shape in ["(1000000000000,)", "(4294967296, 4294967296, 2)"] {
+            let huge = npy_file(
+                &std::format!(
+                    "{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}\n",
+                    shape
+                ),
+                &[0; 16],
+            );
+            assert!(read_npy(huge.as_slice()).is_err());
+        }
+    }
+}
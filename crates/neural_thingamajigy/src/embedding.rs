@@ -0,0 +1,316 @@
+use nalgebra::{RealField, SMatrix, SVector};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{activators::Activator, LayerInfo, Network, NetworkInfo};
+
+/// Looks up a learned vector of `DIM` values for each of `VOCAB` tokens, such as categories or
+/// words. This replaces one-hot encoding, which would need a `VOCAB` wide [crate::Layer].
+///
+/// As a network, each input is a token's index(e.g. `3.0` for token 3), and the output is the
+/// vector of every input token, one after another. So it has `TOKENS` inputs and
+/// `TOKENS * DIM` outputs, and can be chained in front of a `network!` with that many inputs.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Embedding<T: RealField, const VOCAB: usize, const DIM: usize> {
+    /// The vector of each token, as columns
+    pub vectors: SMatrix<T, DIM, VOCAB>,
+}
+
+impl<T: RealField + Copy, const VOCAB: usize, const DIM: usize> Embedding<T, VOCAB, DIM> {
+    /// Creates an embedding from the vector of each token
+    pub fn new(vectors: SMatrix<T, DIM, VOCAB>) -> Self {
+        Self { vectors }
+    }
+
+    /// Returns the vector of token
+    /// # Panics
+    /// If token isn't less than `VOCAB`
+    pub fn lookup(&self, token: usize) -> SVector<T, DIM> {
+        self.vectors.column(token).into_owned()
+    }
+
+    /// Converts an input into a token index
+    /// # Panics
+    /// If the input isn't an index less than `VOCAB`
+    fn token(input: T) -> usize {
+        let index = nalgebra::try_convert::<T, f64>(input.round()).unwrap_or(f64::NAN);
+        assert!(
+            index >= 0. && index < VOCAB as f64,
+            "embedding input {} is not a token index",
+            index
+        );
+
+        index as usize
+    }
+
+    /// Panics at compile time if OUTPUTS isn't `TOKENS * DIM`
+    const fn check_width<const TOKENS: usize, const OUTPUTS: usize>() {
+        assert!(
+            OUTPUTS == TOKENS * DIM,
+            "embedding outputs must be TOKENS * DIM"
+        );
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const VOCAB: usize,
+        const DIM: usize,
+        const TOKENS: usize,
+        const OUTPUTS: usize,
+    > Network<T, TOKENS, OUTPUTS> for Embedding<T, VOCAB, DIM>
+{
+    fn evaluate(&self, inputs: SVector<T, TOKENS>, _: &impl Activator<T>) -> SVector<T, OUTPUTS> {
+        const { Self::check_width::<TOKENS, OUTPUTS>() };
+
+        let mut outputs = SVector::<T, OUTPUTS>::zeros();
+        for (input, output) in inputs.iter().zip(outputs.as_mut_slice().chunks_mut(DIM)) {
+            output.copy_from_slice(self.vectors.column(Self::token(*input)).as_slice());
+        }
+
+        outputs
+    }
+}
+
+impl<
+        T: RealField,
+        const VOCAB: usize,
+        const DIM: usize,
+        const TOKENS: usize,
+        const OUTPUTS: usize,
+    > NetworkInfo<TOKENS, OUTPUTS> for Embedding<T, VOCAB, DIM>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        core::iter::once(LayerInfo {
+            name: "embedding",
+            kind: "Embedding",
+            inputs: TOKENS,
+            outputs: OUTPUTS,
+            parameters: VOCAB * DIM,
+            trainable_parameters: VOCAB * DIM,
+        })
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::{TrainableNetwork, ValueSet};
+#[cfg(feature = "random")]
+use {
+    crate::RandomisableNetwork,
+    rand::{distributions::Standard, prelude::Distribution, Rng},
+};
+
+/// The gradient of an [Embedding], which is only non-zero for the tokens that were looked up.
+/// Combining gradients(e.g. summing) combines which tokens were touched, and [Embedding] only
+/// updates the vectors of touched tokens. Optimisers keep only the tokens touched by the gradient
+/// they transform(see [ValueSet::with_sparsity_of]), so momentum from earlier steps never moves a
+/// token that wasn't looked up in this one.
+#[cfg(feature = "backprop")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmbeddingGradient<T: RealField, const VOCAB: usize, const DIM: usize> {
+    /// The gradient of each token's vector
+    pub vectors: SMatrix<T, DIM, VOCAB>,
+    /// Whether each token was looked up
+    pub touched: SVector<bool, VOCAB>,
+}
+
+#[cfg(feature = "backprop")]
+impl<T: RealField + Copy, const VOCAB: usize, const DIM: usize> ValueSet<T>
+    for EmbeddingGradient<T, VOCAB, DIM>
+{
+    fn unary_operation(&self, f: impl Fn(&T) -> T) -> Self {
+        Self {
+            vectors: self.vectors.unary_operation(f),
+            touched: self.touched,
+        }
+    }
+
+    fn binary_operation(&self, other: &Self, f: impl Fn(&T, &T) -> T) -> Self {
+        Self {
+            vectors: self.vectors.binary_operation(&other.vectors, f),
+            touched: self.touched.zip_map(&other.touched, |a, b| a || b),
+        }
+    }
+
+    fn unary_inspection(&self, f: &mut impl FnMut(&T)) {
+        self.vectors.unary_inspection(f);
+    }
+
+    fn binary_inspection(&self, other: &Self, f: &mut impl FnMut(&T, &T)) {
+        self.vectors.binary_inspection(&other.vectors, f);
+    }
+
+    fn all(v: T) -> Self {
+        Self {
+            vectors: SMatrix::all(v),
+            touched: SVector::repeat(true),
+        }
+    }
+
+    fn with_sparsity_of(&self, gradient: &Self) -> Self {
+        let mut vectors = self.vectors;
+        for token in (0..VOCAB).filter(|&token| !gradient.touched[token]) {
+            vectors.column_mut(token).fill(T::zero());
+        }
+
+        Self {
+            vectors,
+            touched: gradient.touched,
+        }
+    }
+}
+
+#[cfg(feature = "backprop")]
+impl<T: RealField + Copy, const VOCAB: usize, const DIM: usize> Default
+    for EmbeddingGradient<T, VOCAB, DIM>
+{
+    fn default() -> Self {
+        Self {
+            vectors: SMatrix::zeros(),
+            touched: SVector::repeat(false),
+        }
+    }
+}
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const VOCAB: usize,
+        const DIM: usize,
+        const TOKENS: usize,
+        const OUTPUTS: usize,
+    > TrainableNetwork<T, TOKENS, OUTPUTS> for Embedding<T, VOCAB, DIM>
+{
+    type LayerInputs = SVector<T, TOKENS>;
+
+    type Gradient = EmbeddingGradient<T, VOCAB, DIM>;
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, TOKENS>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, OUTPUTS>, Self::LayerInputs) {
+        (self.evaluate(inputs, activator), inputs)
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, OUTPUTS>,
+        _: &impl Activator<T>,
+    ) -> (Self::Gradient, SVector<T, TOKENS>) {
+        let mut gradient = EmbeddingGradient::<T, VOCAB, DIM>::default();
+
+        for (input, loss_gradients) in layer_inputs
+            .iter()
+            .zip(output_loss_gradients.as_slice().chunks(DIM))
+        {
+            let token = Self::token(*input);
+            let mut column = gradient.vectors.column_mut(token);
+            column += SVector::<T, DIM>::from_column_slice(loss_gradients);
+            gradient.touched[token] = true;
+        }
+
+        // token indices can't be nudged, so there is no gradient to pass back
+        (gradient, SVector::zeros())
+    }
+
+    fn apply_nudge(&mut self, nudge: Self::Gradient) {
+        for token in (0..VOCAB).filter(|&token| nudge.touched[token]) {
+            let mut column = self.vectors.column_mut(token);
+            column += nudge.vectors.column(token);
+        }
+    }
+}
+
+#[cfg(feature = "random")]
+impl<T: RealField + Copy, const VOCAB: usize, const DIM: usize> RandomisableNetwork<T>
+    for Embedding<T, VOCAB, DIM>
+where
+    Standard: Distribution<T>,
+{
+    fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            vectors: SMatrix::from_iterator(rng.sample_iter(Standard)),
+        }
+    }
+}
+
+/// Tests
+#[cfg(feature = "std-train")]
+mod test {
+    #[test]
+    fn embedding_test() {
+        use super::Embedding;
+        use crate::{activators::Linear, optimiser::*, Network, TrainableNetwork, ValueSet};
+        use nalgebra::{Matrix2x4, Vector2, Vector4};
+
+        let mut embedding = Embedding::new(Matrix2x4::new(1f64, 2., 3., 4., 5., 6., 7., 8.));
+        let before = embedding.clone();
+
+        let inputs = Vector2::new(2., 0.);
+        let (outputs, layer_inputs): (Vector4<f64>, _) =
+            embedding.evaluate_training(inputs, &Linear);
+        assert_eq!(outputs, Vector4::new(3., 7., 1., 5.));
+        assert_eq!(
+            Network::<f64, 2, 4>::evaluate(&embedding, inputs, &Linear),
+            outputs
+        );
+
+        let (gradient, _) = TrainableNetwork::<f64, 2, 4>::get_gradient(
+            &embedding,
+            &layer_inputs,
+            Vector4::new(1., 1., 1., 1.),
+            &Linear,
+        );
+        assert_eq!(gradient.touched, Vector4::new(true, false, true, false));
+
+        let mut optimiser = AdamOptimiser::default();
+        let step = optimiser.transform(&gradient);
+        TrainableNetwork::<f64, 2, 4>::apply_nudge(&mut embedding, step);
+
+        // only the looked up tokens should change
+        assert_ne!(embedding.lookup(0), before.lookup(0));
+        assert_eq!(embedding.lookup(1), before.lookup(1));
+        assert_ne!(embedding.lookup(2), before.lookup(2));
+        assert_eq!(embedding.lookup(3), before.lookup(3));
+
+        let mut count = 0;
+        gradient.unary_inspection(&mut |_| count += 1);
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn sparse_adam_test() {
+        use super::Embedding;
+        use crate::{activators::Linear, optimiser::*, TrainableNetwork};
+        use nalgebra::{Matrix2x4, Vector1, Vector2};
+
+        let mut embedding = Embedding::new(Matrix2x4::new(1f64, 2., 3., 4., 5., 6., 7., 8.));
+        let mut optimiser = AdamOptimiser::default();
+
+        // look up token 0 for a few steps to build up momentum, then only token 1
+        for token in [0., 0., 0., 1., 1.] {
+            let before = embedding.clone();
+            let (gradient, _) = TrainableNetwork::<f64, 1, 2>::get_gradient(
+                &embedding,
+                &Vector1::new(token),
+                Vector2::new(1., 1.),
+                &Linear,
+            );
+            let step = optimiser.transform(&gradient);
+            TrainableNetwork::<f64, 1, 2>::apply_nudge(&mut embedding, step);
+
+            for other in 0..4 {
+                if other as f64 == token {
+                    assert_ne!(embedding.lookup(other), before.lookup(other));
+                } else {
+                    assert_eq!(embedding.lookup(other), before.lookup(other));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use nalgebra::{RealField, SMatrix, SVector};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{activators::Activator, LayerInfo, Network, NetworkInfo};
+
+/// A network which isn't changed by training, e.g. a pretrained feature extractor chained into a
+/// new head. Its gradient is `()`, so nothing is calculated, accumulated, kept by optimisers or
+/// applied for it, while the gradient of its inputs is still backpropogated(using
+/// [crate::TrainableNetwork::get_input_gradient]), so it can be anywhere in a
+/// [crate::ChainedNetwork].
+/// ## Example
+/// ```rust
+///     use neural_thingamajigy::{network, ChainableNetwork, Frozen, NetworkInfo, RandomisableNetwork};
+///     use rand::rngs::OsRng;
+///
+///     network!(pub Extractor, f32, 8, 4);
+///     network!(pub Head, f32, 4, 1);
+///
+///     let mut extractor = Extractor::random(&mut OsRng);
+///     let mut head = Head::random(&mut OsRng);
+///
+///     // training fine_tuned only changes head
+///     let fine_tuned = Frozen(&mut extractor).chain(&mut head);
+///     assert_eq!(fine_tuned.trainable_parameter_count(), 4 + 1);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frozen<N>(pub N);
+
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const OUTPUTS: usize,
+        N: Network<T, INPUTS, OUTPUTS>,
+    > Network<T, INPUTS, OUTPUTS> for Frozen<N>
+{
+    fn evaluate(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, OUTPUTS> {
+        self.0.evaluate(inputs, activator)
+    }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, OUTPUTS, B> {
+        self.0.evaluate_batch(inputs, activator)
+    }
+}
+
+impl<const INPUTS: usize, const OUTPUTS: usize, N: NetworkInfo<INPUTS, OUTPUTS>>
+    NetworkInfo<INPUTS, OUTPUTS> for Frozen<N>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        self.0.layer_info().map(|layer| LayerInfo {
+            trainable_parameters: 0,
+            ..layer
+        })
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::{BatchTrainableNetwork, TrainableNetwork};
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const OUTPUTS: usize,
+        N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    > TrainableNetwork<T, INPUTS, OUTPUTS> for Frozen<N>
+{
+    type LayerInputs = N::LayerInputs;
+
+    type Gradient = ();
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, OUTPUTS>, Self::LayerInputs) {
+        self.0.evaluate_training(inputs, activator)
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, OUTPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SVector<T, INPUTS>) {
+        (
+            (),
+            self.0
+                .get_input_gradient(layer_inputs, output_loss_gradients, activator),
+        )
+    }
+
+    fn get_input_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, OUTPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.0
+            .get_input_gradient(layer_inputs, output_loss_gradients, activator)
+    }
+
+    fn apply_nudge(&mut self, _: Self::Gradient) {}
+}
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const INPUTS: usize,
+        const OUTPUTS: usize,
+        N: BatchTrainableNetwork<T, INPUTS, OUTPUTS>,
+    > BatchTrainableNetwork<T, INPUTS, OUTPUTS> for Frozen<N>
+{
+    type BatchLayerInputs<const B: usize> = N::BatchLayerInputs<B>;
+
+    fn evaluate_training_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (SMatrix<T, OUTPUTS, B>, Self::BatchLayerInputs<B>) {
+        self.0.evaluate_training_batch(inputs, activator)
+    }
+
+    fn get_gradient_batch<const B: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<B>,
+        output_loss_gradients: SMatrix<T, OUTPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SMatrix<T, INPUTS, B>) {
+        (
+            (),
+            self.0
+                .get_input_gradient_batch(layer_inputs, output_loss_gradients, activator),
+        )
+    }
+
+    fn get_input_gradient_batch<const B: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<B>,
+        output_loss_gradients: SMatrix<T, OUTPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        self.0
+            .get_input_gradient_batch(layer_inputs, output_loss_gradients, activator)
+    }
+}
@@ -0,0 +1,218 @@
+extern crate std;
+
+use crate::{
+    activators::{Activator, Elu, Relu},
+    data::{DataError, NpyArray},
+    Layer, WeightLayout,
+};
+use nalgebra::RealField;
+use std::{borrow::ToOwned, format, io::Read, string::String, vec, vec::Vec};
+
+/// Defines DynNetwork, which evaluates imported layers the same way as the framework they came from
+mod dynamic;
+/// Reads the dense layers of ONNX models
+pub mod onnx;
+/// Reads safetensors files, e.g. PyTorch state-dicts
+pub mod safetensors;
+
+pub use {dynamic::DynNetwork, onnx::*, safetensors::*};
+
+/// An activation applied by an imported layer, after adding its bias
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Activation {
+    /// `max(x, 0)`
+    Relu,
+    /// `x`, or `alpha * x` below zero
+    LeakyRelu(f64),
+    /// `1 / (1 + e^-x)`
+    Sigmoid,
+    /// `x`, or `alpha * (e^x - 1)` below zero
+    Elu(f64),
+}
+
+impl Activation {
+    /// Applies the activation to x
+    pub fn apply<T: RealField + Copy>(&self, x: T) -> T {
+        match *self {
+            Activation::Relu => Relu::default().activation(x),
+            Activation::LeakyRelu(alpha) => Relu {
+                leaky_gradient: nalgebra::convert(alpha),
+            }
+            .activation(x),
+            // the Sigmoid activator gives 1 / (1 + e^x)
+            Activation::Sigmoid => T::one() / (T::one() + (-x).exp()),
+            // the Elu activator always has an alpha of 1
+            Activation::Elu(alpha) if x < T::zero() => {
+                nalgebra::convert::<f64, T>(alpha) * Elu.activation(x)
+            }
+            Activation::Elu(_) => x,
+        }
+    }
+}
+
+/// A fully connected layer exported by another framework
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseLayer {
+    /// Where the layer came from, e.g. its state-dict prefix or ONNX node name
+    pub name: String,
+    /// How many values go into the layer
+    pub inputs: usize,
+    /// How many values come out of the layer
+    pub outputs: usize,
+    /// The weights in row-major `[outputs, inputs]` order, like PyTorch's `nn.Linear.weight`
+    pub weight: Vec<f64>,
+    /// The bias of each output, which the other framework adds before the activation
+    pub bias: Vec<f64>,
+    /// The activation applied to the outputs, or None if there is none(or it isn't known, as
+    /// state-dicts only hold parameters)
+    pub activation: Option<Activation>,
+}
+
+/// A network that can be built from the dense layers of a network trained in another framework,
+/// implemented by [DynNetwork] and `network!`.
+///
+/// [DynNetwork] evaluates any chain of layers the same way as the framework they came from, e.g.
+/// an MLP of `nn.Linear` and `nn.ReLU`. A `network!` is fixed size, but its layers add their bias
+/// after activating and it applies one activator to every layer, so importing into one fails with
+/// [DataError::Unsupported] when the layers have different activations, or when a layer has both
+/// a bias and an activation.
+pub trait ImportableNetwork: Sized {
+    /// Builds the network from layers, in the order they're evaluated
+    fn from_dense_layers(layers: &[DenseLayer]) -> Result<Self, DataError>;
+
+    /// Builds the network from the dense layers of an ONNX model, see [read_onnx]
+    fn from_onnx(reader: impl Read) -> Result<Self, DataError> {
+        Self::from_dense_layers(&read_onnx(reader)?)
+    }
+
+    /// Builds the network from the `{prefix}.weight` and `{prefix}.bias` tensors of a state-dict,
+    /// see [state_dict_layers]. State-dicts don't hold activations, so to import a model with
+    /// activations, set them on the layers from [state_dict_layers] and use
+    /// [Self::from_dense_layers].
+    fn from_state_dict(
+        state_dict: &[(String, NpyArray)],
+        prefixes: &[&str],
+    ) -> Result<Self, DataError> {
+        Self::from_dense_layers(&state_dict_layers(state_dict, prefixes)?)
+    }
+}
+
+/// Finds the layer with each prefix in a state-dict(e.g. `["fc1", "fc2"]`, or `["0", "2"]` for
+/// an `nn.Sequential`), which must have a 2d `{prefix}.weight` and may have a `{prefix}.bias`
+pub fn state_dict_layers(
+    state_dict: &[(String, NpyArray)],
+    prefixes: &[&str],
+) -> Result<Vec<DenseLayer>, DataError> {
+    let tensor = |name: &str| {
+        state_dict
+            .iter()
+            .find(|(tensor, _)| tensor == name)
+            .map(|(_, array)| array)
+    };
+
+    prefixes
+        .iter()
+        .map(|&prefix| {
+            let weight = tensor(&format!("{}.weight", prefix))
+                .ok_or_else(|| DataError::Format(format!("no tensor named {}.weight", prefix)))?;
+            let &[outputs, inputs] = weight.shape.as_slice() else {
+                return Err(DataError::Format(format!(
+                    "{}.weight has shape {:?}, but should be 2d",
+                    prefix, weight.shape
+                )));
+            };
+
+            let bias = match tensor(&format!("{}.bias", prefix)) {
+                Some(bias) if bias.data.len() != outputs => {
+                    return Err(DataError::WidthMismatch {
+                        context: format!("{}.bias", prefix),
+                        expected: outputs,
+                        found: bias.data.len(),
+                    })
+                }
+                Some(bias) => bias.data.clone(),
+                None => vec![0.; outputs],
+            };
+
+            Ok(DenseLayer {
+                name: prefix.to_owned(),
+                inputs,
+                outputs,
+                weight: weight.data.clone(),
+                bias,
+                activation: None,
+            })
+        })
+        .collect()
+}
+
+/// Checks there are count layers, which a network of [Layer]s can evaluate the same way as the
+/// framework they came from
+pub fn check_dense_layers(layers: &[DenseLayer], count: usize) -> Result<(), DataError> {
+    if layers.len() != count {
+        return Err(DataError::WidthMismatch {
+            context: "layers".to_owned(),
+            expected: count,
+            found: layers.len(),
+        });
+    }
+
+    for layer in layers {
+        if layer.activation != layers[0].activation {
+            return Err(DataError::Unsupported(format!(
+                "{} and {} have different activations, but networks use one activator, so import \
+                 them as a DynNetwork",
+                layers[0].name, layer.name
+            )));
+        }
+
+        if let (Some(activation), true) = (&layer.activation, layer.bias.iter().any(|&b| b != 0.)) {
+            return Err(DataError::Unsupported(format!(
+                "{} adds a bias before its {:?} activation, but layers add it after, so import \
+                 it as a DynNetwork",
+                layer.name, activation
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts an imported layer into a [Layer], checking it has `INPUTS` inputs and `OUTPUTS`
+/// outputs
+pub fn dense_layer<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    layer: &DenseLayer,
+) -> Result<Layer<T, INPUTS, OUTPUTS>, DataError> {
+    for (context, expected, found) in [
+        ("inputs", INPUTS, layer.inputs),
+        ("outputs", OUTPUTS, layer.outputs),
+    ] {
+        if expected != found {
+            return Err(DataError::WidthMismatch {
+                context: format!("the {} of {}", context, layer.name),
+                expected,
+                found,
+            });
+        }
+    }
+
+    let convert =
+        |values: &[f64]| -> Vec<T> { values.iter().map(|&v| nalgebra::convert(v)).collect() };
+    Layer::from_slices(
+        &convert(&layer.weight),
+        &convert(&layer.bias),
+        WeightLayout::OutputsByInputs,
+    )
+    .map_err(|e| DataError::WidthMismatch {
+        context: format!("the {} of {}", e.part, layer.name),
+        expected: e.expected,
+        found: e.found,
+    })
+}
+
+/// Transposes a row-major `[rows, columns]` matrix
+fn transpose(values: &[f64], rows: usize, columns: usize) -> Vec<f64> {
+    (0..columns * rows)
+        .map(|i| values[(i % rows) * columns + i / rows])
+        .collect()
+}
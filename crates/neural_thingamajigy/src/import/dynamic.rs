@@ -0,0 +1,113 @@
+extern crate std;
+
+use super::{Activation, DenseLayer, ImportableNetwork};
+use crate::{activators::Activator, data::DataError, LayerInfo, Network, NetworkInfo};
+use nalgebra::{DMatrix, DVector, RealField, SVector};
+use std::{borrow::ToOwned, format, vec::Vec};
+
+/// A network of imported layers, which add their bias before their own activation like another
+/// framework's dense layers(e.g. PyTorch's `nn.Linear` followed by `nn.ReLU`).
+///
+/// The widths of the layers in between are only known at runtime, but importing checks they take
+/// `INPUTS` values and give `OUTPUTS` values, so it can be evaluated or chained like any other
+/// network. Each layer applies its own activation, so the activator passed to it is ignored. It
+/// can't be trained, so train a `network!` instead when a model is trained by this crate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynNetwork<T: RealField, const INPUTS: usize, const OUTPUTS: usize> {
+    /// The layers, in the order they're evaluated
+    layers: Vec<DynLayer<T>>,
+}
+
+/// A layer of a [DynNetwork]
+#[derive(Clone, Debug, PartialEq)]
+struct DynLayer<T: RealField> {
+    /// The weights, with a row for each output
+    weight: DMatrix<T>,
+    /// The bias of each output, added before the activation
+    bias: DVector<T>,
+    /// The activation applied to the outputs, if any
+    activation: Option<Activation>,
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> ImportableNetwork
+    for DynNetwork<T, INPUTS, OUTPUTS>
+{
+    fn from_dense_layers(layers: &[DenseLayer]) -> Result<Self, DataError> {
+        let mut width = INPUTS;
+        let layers = layers
+            .iter()
+            .map(|layer| {
+                for (context, expected, found) in [
+                    ("inputs", width, layer.inputs),
+                    (
+                        "weight",
+                        layer.inputs.saturating_mul(layer.outputs),
+                        layer.weight.len(),
+                    ),
+                    ("bias", layer.outputs, layer.bias.len()),
+                ] {
+                    if expected != found {
+                        return Err(DataError::WidthMismatch {
+                            context: format!("the {} of {}", context, layer.name),
+                            expected,
+                            found,
+                        });
+                    }
+                }
+                width = layer.outputs;
+
+                let convert = |&v: &f64| nalgebra::convert(v);
+                Ok(DynLayer {
+                    weight: DMatrix::from_row_iterator(
+                        layer.outputs,
+                        layer.inputs,
+                        layer.weight.iter().map(convert),
+                    ),
+                    bias: DVector::from_iterator(layer.outputs, layer.bias.iter().map(convert)),
+                    activation: layer.activation,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if width != OUTPUTS {
+            return Err(DataError::WidthMismatch {
+                context: "the outputs of the last layer".to_owned(),
+                expected: OUTPUTS,
+                found: width,
+            });
+        }
+
+        Ok(Self { layers })
+    }
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Network<T, INPUTS, OUTPUTS>
+    for DynNetwork<T, INPUTS, OUTPUTS>
+{
+    fn evaluate(&self, inputs: SVector<T, INPUTS>, _: &impl Activator<T>) -> SVector<T, OUTPUTS> {
+        let mut values = DVector::from_column_slice(inputs.as_slice());
+        for layer in &self.layers {
+            values = &layer.weight * values + &layer.bias;
+            if let Some(activation) = layer.activation {
+                values.apply(|v| *v = activation.apply(*v));
+            }
+        }
+
+        SVector::from_column_slice(values.as_slice())
+    }
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> NetworkInfo<INPUTS, OUTPUTS>
+    for DynNetwork<T, INPUTS, OUTPUTS>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        self.layers.iter().map(|layer| LayerInfo {
+            name: "imported",
+            kind: "DynLayer",
+            inputs: layer.weight.ncols(),
+            outputs: layer.weight.nrows(),
+            parameters: layer.weight.len() + layer.bias.len(),
+            trainable_parameters: 0,
+        })
+    }
+}
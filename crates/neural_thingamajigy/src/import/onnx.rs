@@ -0,0 +1,631 @@
+extern crate std;
+
+use super::{transpose, Activation, DenseLayer};
+use crate::data::{npy::checked_product, DataError, NpyArray};
+use std::{
+    borrow::ToOwned,
+    format,
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+/// The ONNX operators which are kept as the activation of the layer before them
+pub const ONNX_ACTIVATIONS: [&str; 4] = ["Relu", "LeakyRelu", "Sigmoid", "Elu"];
+
+/// Reads the dense layers of an ONNX model, which must be a chain of `Gemm` or `MatMul`(and
+/// optionally `Add`) nodes whose weights are initializers, each optionally followed by one of
+/// [ONNX_ACTIVATIONS]. `Identity` nodes are skipped, and any other node is
+/// [DataError::Unsupported].
+pub fn read_onnx(mut reader: impl Read) -> Result<Vec<DenseLayer>, DataError> {
+    let mut model = Vec::new();
+    reader.read_to_end(&mut model)?;
+
+    let mut graph = None;
+    for field in Fields(&model) {
+        if let (7, Value::Bytes(bytes)) = field? {
+            graph = Some(bytes);
+        }
+    }
+    let graph = graph.ok_or_else(|| DataError::Format("the model has no graph".to_owned()))?;
+
+    let mut nodes = Vec::new();
+    let mut initializers = Vec::new();
+    let mut inputs = Vec::new();
+    for field in Fields(graph) {
+        match field? {
+            (1, Value::Bytes(bytes)) => nodes.push(Node::parse(bytes)?),
+            (5, Value::Bytes(bytes)) => initializers.push(tensor(bytes)?),
+            (11, Value::Bytes(bytes)) => inputs.push(name(bytes)?),
+            _ => {}
+        }
+    }
+    let initializer = |name: &str| {
+        initializers
+            .iter()
+            .find(|(tensor, _)| tensor == name)
+            .map(|(_, tensor)| tensor)
+    };
+
+    // the one input that isn't an initializer is what flows through the chain
+    let mut current = inputs
+        .into_iter()
+        .find(|input| initializer(input).is_none())
+        .ok_or_else(|| DataError::Format("the graph has no input".to_owned()))?;
+    let mut layers: Vec<DenseLayer> = Vec::new();
+
+    for node in nodes {
+        // the other input of a node taking the current value, which must be an initializer
+        let other = |position: usize| {
+            let name = node.inputs.get(position).ok_or_else(|| {
+                DataError::Format(format!("{} is missing input {}", node.name, position))
+            })?;
+            initializer(name).ok_or_else(|| {
+                DataError::Unsupported(format!(
+                    "{} takes {:?}, which isn't constant",
+                    node.name, name
+                ))
+            })
+        };
+        if node.inputs.first() != Some(&current) && node.op_type != "Add" {
+            return Err(DataError::Unsupported(format!(
+                "{} doesn't take the output of the node before it",
+                node.name
+            )));
+        }
+
+        match node.op_type.as_str() {
+            "Gemm" => {
+                if node.int("transA", 0) != 0 {
+                    return Err(DataError::Unsupported(format!(
+                        "{} transposes its inputs",
+                        node.name
+                    )));
+                }
+
+                // Y = alpha * A * B + beta * C, where B is [inputs, outputs] unless transB is set
+                let weight = other(1)?;
+                let &[rows, columns] = weight.shape.as_slice() else {
+                    return Err(DataError::Format(format!(
+                        "{}'s weight isn't 2d",
+                        node.name
+                    )));
+                };
+                let alpha = node.float("alpha", 1.);
+                let (inputs, outputs, weight) = match node.int("transB", 0) {
+                    0 => (rows, columns, transpose(&weight.data, rows, columns)),
+                    _ => (columns, rows, weight.data.clone()),
+                };
+
+                let bias = match node.inputs.get(2).filter(|name| !name.is_empty()) {
+                    Some(_) => other(2)?.data.clone(),
+                    None => vec![0.; outputs],
+                };
+                let beta = node.float("beta", 1.);
+                if bias.len() != outputs {
+                    return Err(DataError::Unsupported(format!(
+                        "{}'s bias isn't a vector of its outputs",
+                        node.name
+                    )));
+                }
+
+                layers.push(DenseLayer {
+                    name: node.name.clone(),
+                    inputs,
+                    outputs,
+                    weight: weight.iter().map(|w| w * alpha).collect(),
+                    bias: bias.iter().map(|b| b * beta).collect(),
+                    activation: None,
+                });
+            }
+            "MatMul" => {
+                let weight = other(1)?;
+                let &[inputs, outputs] = weight.shape.as_slice() else {
+                    return Err(DataError::Format(format!(
+                        "{}'s weight isn't 2d",
+                        node.name
+                    )));
+                };
+
+                layers.push(DenseLayer {
+                    name: node.name.clone(),
+                    inputs,
+                    outputs,
+                    weight: transpose(&weight.data, inputs, outputs),
+                    bias: vec![0.; outputs],
+                    activation: None,
+                });
+            }
+            "Add" => {
+                // the bias of the MatMul before it, which may be either input
+                let position = node.inputs.iter().position(|input| *input == current);
+                let layer = layers.last_mut().filter(|layer| layer.activation.is_none());
+                let (Some(position), Some(layer)) = (position, layer) else {
+                    return Err(DataError::Unsupported(format!(
+                        "{} doesn't add to the output of a layer",
+                        node.name
+                    )));
+                };
+                let bias = other(1 - position)?;
+                if bias.data.len() != layer.outputs {
+                    return Err(DataError::Unsupported(format!(
+                        "{} doesn't add a vector of its layer's outputs",
+                        node.name
+                    )));
+                }
+
+                for (b, added) in layer.bias.iter_mut().zip(&bias.data) {
+                    *b += added;
+                }
+            }
+            op if ONNX_ACTIVATIONS.contains(&op) => {
+                match layers.last_mut() {
+                    Some(layer) if layer.activation.is_none() => {
+                        layer.activation = Some(match op {
+                            "Relu" => Activation::Relu,
+                            "LeakyRelu" => Activation::LeakyRelu(node.float("alpha", 0.01)),
+                            "Sigmoid" => Activation::Sigmoid,
+                            // the last of ONNX_ACTIVATIONS
+                            _ => Activation::Elu(node.float("alpha", 1.)),
+                        });
+                    }
+                    _ => {
+                        return Err(DataError::Unsupported(format!(
+                            "{} doesn't follow a layer",
+                            node.name
+                        )))
+                    }
+                };
+            }
+            "Identity" => {}
+            op => return Err(DataError::Unsupported(format!("ONNX operator {}", op))),
+        }
+
+        current = node
+            .outputs
+            .first()
+            .ok_or_else(|| DataError::Format(format!("{} has no output", node.name)))?
+            .clone();
+    }
+
+    Ok(layers)
+}
+
+/// Loads the dense layers of the ONNX model at path, see [read_onnx]
+pub fn load_onnx(path: impl AsRef<Path>) -> Result<Vec<DenseLayer>, DataError> {
+    read_onnx(BufReader::new(File::open(path)?))
+}
+
+/// The parts of an ONNX `NodeProto` needed to follow the graph
+struct Node {
+    /// The names of the values taken
+    inputs: Vec<String>,
+    /// The names of the values produced
+    outputs: Vec<String>,
+    /// The node's name, or its operator if it's unnamed
+    name: String,
+    /// The operator, e.g. `Gemm`
+    op_type: String,
+    /// Integer and float attributes
+    attributes: Vec<(String, Value<'static>)>,
+}
+
+impl Node {
+    /// Parses a `NodeProto`
+    fn parse(bytes: &[u8]) -> Result<Self, DataError> {
+        let mut node = Node {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            name: String::new(),
+            op_type: String::new(),
+            attributes: Vec::new(),
+        };
+
+        for field in Fields(bytes) {
+            match field? {
+                (1, Value::Bytes(input)) => node.inputs.push(string(input)?),
+                (2, Value::Bytes(output)) => node.outputs.push(string(output)?),
+                (3, Value::Bytes(name)) => node.name = string(name)?,
+                (4, Value::Bytes(op_type)) => node.op_type = string(op_type)?,
+                (5, Value::Bytes(attribute)) => {
+                    let mut name = String::new();
+                    let mut value = None;
+                    for field in Fields(attribute) {
+                        match field? {
+                            (1, Value::Bytes(n)) => name = string(n)?,
+                            (2, Value::Fixed32(f)) => value = Some(Value::Fixed32(f)),
+                            (3, Value::Varint(i)) => value = Some(Value::Varint(i)),
+                            _ => {}
+                        }
+                    }
+                    if let Some(value) = value {
+                        node.attributes.push((name, value));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if node.name.is_empty() {
+            node.name = node.op_type.clone();
+        }
+        Ok(node)
+    }
+
+    /// The integer attribute called name, or default if it isn't set
+    fn int(&self, name: &str, default: i64) -> i64 {
+        match self.attributes.iter().find(|(n, _)| n == name) {
+            Some((_, Value::Varint(i))) => *i as i64,
+            _ => default,
+        }
+    }
+
+    /// The float attribute called name, or default if it isn't set
+    fn float(&self, name: &str, default: f64) -> f64 {
+        match self.attributes.iter().find(|(n, _)| n == name) {
+            Some((_, Value::Fixed32(f))) => f32::from_bits(*f) as f64,
+            _ => default,
+        }
+    }
+}
+
+/// Parses a `TensorProto` into its name and values
+fn tensor(bytes: &[u8]) -> Result<(String, NpyArray), DataError> {
+    let mut name = String::new();
+    let mut shape = Vec::new();
+    let mut data_type = 0;
+    let mut data = Vec::new();
+    let mut raw = None;
+
+    for field in Fields(bytes) {
+        match field? {
+            (1, Value::Varint(dim)) => shape.push(dim as usize),
+            (1, Value::Bytes(dims)) => {
+                let mut dims = dims;
+                while !dims.is_empty() {
+                    shape.push(varint(&mut dims)? as usize);
+                }
+            }
+            (2, Value::Varint(t)) => data_type = t,
+            (4, Value::Fixed32(f)) => data.push(f32::from_bits(f) as f64),
+            (4, Value::Bytes(floats)) => data.extend(
+                floats
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes(b.try_into().unwrap()) as f64),
+            ),
+            (8, Value::Bytes(n)) => name = string(n)?,
+            (9, Value::Bytes(bytes)) => raw = Some(bytes),
+            (10, Value::Fixed64(d)) => data.push(f64::from_bits(d)),
+            (10, Value::Bytes(doubles)) => data.extend(
+                doubles
+                    .chunks_exact(8)
+                    .map(|b| f64::from_le_bytes(b.try_into().unwrap())),
+            ),
+            (14, Value::Varint(1)) => {
+                return Err(DataError::Unsupported(format!(
+                    "{} is stored in an external file",
+                    name
+                )))
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(raw) = raw {
+        data = match data_type {
+            1 => raw
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes(b.try_into().unwrap()) as f64)
+                .collect(),
+            11 => raw
+                .chunks_exact(8)
+                .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+                .collect(),
+            _ => {
+                return Err(DataError::Unsupported(format!(
+                    "{} has ONNX data type {}",
+                    name, data_type
+                )))
+            }
+        };
+    }
+
+    if data.len() != checked_product(&shape)? {
+        return Err(DataError::Format(format!(
+            "{} has shape {:?}, but {} values",
+            name,
+            shape,
+            data.len()
+        )));
+    }
+    Ok((name, NpyArray { shape, data }))
+}
+
+/// Parses the name of a `ValueInfoProto`
+fn name(bytes: &[u8]) -> Result<String, DataError> {
+    for field in Fields(bytes) {
+        if let (1, Value::Bytes(name)) = field? {
+            return string(name);
+        }
+    }
+
+    Ok(String::new())
+}
+
+/// Parses a protobuf string
+fn string(bytes: &[u8]) -> Result<String, DataError> {
+    String::from_utf8(bytes.to_owned())
+        .map_err(|_| DataError::Format("a string isn't utf-8".to_owned()))
+}
+
+/// A protobuf field's value, by wire type
+#[derive(Clone, Copy)]
+enum Value<'a> {
+    /// Wire type 0
+    Varint(u64),
+    /// Wire type 1
+    Fixed64(u64),
+    /// Wire type 2, e.g. strings, messages and packed numbers
+    Bytes(&'a [u8]),
+    /// Wire type 5
+    Fixed32(u32),
+}
+
+/// Iterates over the `(field number, value)` pairs of a protobuf message
+struct Fields<'a>(&'a [u8]);
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Result<(u64, Value<'a>), DataError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let field = (|| {
+            let key = varint(&mut self.0)?;
+            let value = match key & 7 {
+                0 => Value::Varint(varint(&mut self.0)?),
+                1 => Value::Fixed64(u64::from_le_bytes(
+                    take(&mut self.0, 8)?.try_into().unwrap(),
+                )),
+                2 => {
+                    let length = varint(&mut self.0)? as usize;
+                    Value::Bytes(take(&mut self.0, length)?)
+                }
+                5 => Value::Fixed32(u32::from_le_bytes(
+                    take(&mut self.0, 4)?.try_into().unwrap(),
+                )),
+                wire_type => {
+                    return Err(DataError::Unsupported(format!(
+                        "protobuf wire type {}",
+                        wire_type
+                    )))
+                }
+            };
+            Ok((key >> 3, value))
+        })();
+
+        // stop after an error, rather than reading garbage
+        if field.is_err() {
+            self.0 = &[];
+        }
+        Some(field)
+    }
+}
+
+/// Reads a varint from the start of bytes
+fn varint(bytes: &mut &[u8]) -> Result<u64, DataError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = take(bytes, 1)?[0];
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+
+    Err(DataError::Format(
+        "a protobuf varint is too long".to_owned(),
+    ))
+}
+
+/// Takes count bytes from the start of bytes
+fn take<'a>(bytes: &mut &'a [u8], count: usize) -> Result<&'a [u8], DataError> {
+    if bytes.len() < count {
+        return Err(DataError::Format(
+            "the protobuf message ends early".to_owned(),
+        ));
+    }
+
+    let (taken, rest) = bytes.split_at(count);
+    *bytes = rest;
+    Ok(taken)
+}
+
+/// Tests
+mod test {
+    extern crate std;
+
+    #[cfg(test)]
+    use std::vec::Vec;
+
+    /// Encodes a protobuf varint
+    #[cfg(test)]
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while value >= 0x80 {
+            bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        bytes.push(value as u8);
+        bytes
+    }
+
+    /// Encodes a length delimited field
+    #[cfg(test)]
+    fn field(number: u64, value: &[u8]) -> Vec<u8> {
+        let mut bytes = varint(number << 3 | 2);
+        bytes.extend(varint(value.len() as u64));
+        bytes.extend_from_slice(value);
+        bytes
+    }
+
+    /// Encodes a node, with an optional integer attribute
+    #[cfg(test)]
+    fn node(
+        op_type: &str,
+        inputs: &[&str],
+        output: &str,
+        attribute: Option<(&str, u64)>,
+    ) -> Vec<u8> {
+        let mut node: Vec<u8> = inputs.iter().flat_map(|i| field(1, i.as_bytes())).collect();
+        node.extend(field(2, output.as_bytes()));
+        node.extend(field(4, op_type.as_bytes()));
+        if let Some((name, value)) = attribute {
+            let mut attribute = field(1, name.as_bytes());
+            attribute.extend(varint(3 << 3));
+            attribute.extend(varint(value));
+            node.extend(field(5, &attribute));
+        }
+        field(1, &node)
+    }
+
+    /// Encodes a node taking one input, with a float alpha attribute
+    #[cfg(test)]
+    fn alpha_node(op_type: &str, input: &str, output: &str, alpha: f32) -> Vec<u8> {
+        let mut node = field(1, input.as_bytes());
+        node.extend(field(2, output.as_bytes()));
+        node.extend(field(4, op_type.as_bytes()));
+        let mut attribute = field(1, b"alpha");
+        attribute.extend(varint(2 << 3 | 5));
+        attribute.extend(alpha.to_le_bytes());
+        node.extend(field(5, &attribute));
+        field(1, &node)
+    }
+
+    /// Encodes an f32 initializer, with packed dims and raw data
+    #[cfg(test)]
+    fn initializer(name: &str, dims: &[u64], values: &[f32]) -> Vec<u8> {
+        let dims: Vec<u8> = dims.iter().flat_map(|&d| varint(d)).collect();
+        let raw: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let mut tensor = field(1, &dims);
+        tensor.extend(varint(2 << 3));
+        tensor.extend(varint(1));
+        tensor.extend(field(8, name.as_bytes()));
+        tensor.extend(field(9, &raw));
+        field(5, &tensor)
+    }
+
+    #[test]
+    fn onnx_test() {
+        use super::read_onnx;
+        use crate::{
+            activators::Linear,
+            data::DataError,
+            import::{Activation, DynNetwork, ImportableNetwork},
+            Network,
+        };
+        use nalgebra::{Vector1, Vector2, Vector3};
+
+        // x -> Gemm(transB) -> Relu -> MatMul -> Add -> y, as exported from PyTorch
+        let mut graph = Vec::new();
+        graph.extend(node("Gemm", &["x", "w1", "b1"], "h", Some(("transB", 1))));
+        graph.extend(node("Relu", &["h"], "a", None));
+        graph.extend(node("MatMul", &["a", "w2"], "m", None));
+        graph.extend(node("Add", &["b2", "m"], "y", None));
+        graph.extend(initializer("w1", &[2, 3], &[1., 2., 3., 4., 5., 6.]));
+        graph.extend(initializer("b1", &[2], &[-7., 1.]));
+        graph.extend(initializer("w2", &[2, 1], &[7., 8.]));
+        graph.extend(initializer("b2", &[1], &[0.5]));
+        graph.extend(field(11, &field(1, b"x")));
+        graph.extend(field(11, &field(1, b"w1")));
+        let model = field(7, &graph);
+
+        let layers = read_onnx(model.as_slice()).unwrap();
+        assert_eq!(layers.len(), 2);
+        assert_eq!((layers[0].inputs, layers[0].outputs), (3, 2));
+        assert_eq!(layers[0].weight, [1., 2., 3., 4., 5., 6.]);
+        assert_eq!(layers[0].bias, [-7., 1.]);
+        assert_eq!(layers[0].activation, Some(Activation::Relu));
+        assert_eq!((layers[1].inputs, layers[1].outputs), (2, 1));
+        assert_eq!(layers[1].weight, [7., 8.]);
+        assert_eq!(layers[1].bias, [0.5]);
+        assert_eq!(layers[1].activation, None);
+
+        // the bias is added before the ReLU, so [6 - 7, 15 + 1] becomes [0, 16]
+        let network = DynNetwork::<f64, 3, 1>::from_onnx(model.as_slice()).unwrap();
+        assert_eq!(
+            network.evaluate(Vector3::new(1., 1., 1.), &Linear),
+            Vector1::new(8. * 16. + 0.5)
+        );
+
+        // each activation applied to [6 - 7, 15 + 1], as the ONNX operators define them
+        for (activation, alpha, expected, y) in [
+            (
+                "Sigmoid",
+                None,
+                Activation::Sigmoid,
+                [0.2689414213699951, 0.9999998874648379],
+            ),
+            ("LeakyRelu", None, Activation::LeakyRelu(0.01), [-0.01, 16.]),
+            (
+                "LeakyRelu",
+                Some(0.25),
+                Activation::LeakyRelu(0.25),
+                [-0.25, 16.],
+            ),
+            (
+                "Elu",
+                Some(0.5),
+                Activation::Elu(0.5),
+                [-0.31606027941427883, 16.],
+            ),
+        ] {
+            let mut graph = node("Gemm", &["x", "w1", "b1"], "h", Some(("transB", 1)));
+            graph.extend(match alpha {
+                Some(alpha) => alpha_node(activation, "h", "y", alpha),
+                None => node(activation, &["h"], "y", None),
+            });
+            graph.extend(initializer("w1", &[2, 3], &[1., 2., 3., 4., 5., 6.]));
+            graph.extend(initializer("b1", &[2], &[-7., 1.]));
+            graph.extend(field(11, &field(1, b"x")));
+            let model = field(7, &graph);
+
+            assert_eq!(
+                read_onnx(model.as_slice()).unwrap()[0].activation,
+                Some(expected)
+            );
+            let network = DynNetwork::<f64, 3, 2>::from_onnx(model.as_slice()).unwrap();
+            let outputs = network.evaluate(Vector3::new(1., 1., 1.), &Linear);
+            assert!((outputs - Vector2::new(y[0], y[1])).norm() < 1e-12);
+        }
+
+        // Gemm's weights are [inputs, outputs] without transB
+        let mut graph = node("Gemm", &["x", "w"], "y", None);
+        graph.extend(initializer("w", &[3, 2], &[1., 4., 2., 5., 3., 6.]));
+        graph.extend(field(11, &field(1, b"x")));
+        let layers = read_onnx(field(7, &graph).as_slice()).unwrap();
+        assert_eq!(layers[0].weight, [1., 2., 3., 4., 5., 6.]);
+
+        let mut graph = node("Conv", &["x", "w"], "y", None);
+        graph.extend(field(11, &field(1, b"x")));
+        assert!(matches!(
+            read_onnx(field(7, &graph).as_slice()),
+            Err(DataError::Unsupported(_))
+        ));
+
+        // a shape whose size overflows
+        let mut graph = node("MatMul", &["x", "w"], "y", None);
+        graph.extend(initializer("w", &[1 << 32, 1 << 32], &[]));
+        graph.extend(field(11, &field(1, b"x")));
+        assert!(matches!(
+            read_onnx(field(7, &graph).as_slice()),
+            Err(DataError::Format(_))
+        ));
+    }
+}
@@ -0,0 +1,349 @@
+extern crate std;
+
+use crate::data::{
+    npy::{checked_product, read_length},
+    DataError, NpyArray,
+};
+use std::{
+    borrow::ToOwned,
+    format,
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+    string::String,
+    vec::Vec,
+};
+
+/// The longest header a safetensors file may have, in bytes
+const MAX_HEADER_LENGTH: u64 = 100_000_000;
+
+/// How deeply arrays and objects may be nested in a safetensors header
+const MAX_DEPTH: usize = 64;
+
+/// Reads every tensor in a safetensors file, e.g. one written by `safetensors.torch.save_file`
+/// from a PyTorch state-dict, returning `(name, tensor)` pairs in the order they're stored
+pub fn read_safetensors(mut reader: impl Read) -> Result<Vec<(String, NpyArray)>, DataError> {
+    let mut header_length = [0u8; 8];
+    reader.read_exact(&mut header_length)?;
+    let header_length = u64::from_le_bytes(header_length);
+    if header_length > MAX_HEADER_LENGTH {
+        return Err(DataError::Format(format!(
+            "the safetensors header is {} bytes, but can be at most {}",
+            header_length, MAX_HEADER_LENGTH
+        )));
+    }
+
+    let header = read_length(&mut reader, header_length)?;
+    let header = String::from_utf8(header)
+        .map_err(|_| DataError::Format("the safetensors header isn't utf-8".to_owned()))?;
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    let Json::Object(entries) = Json::parse(&header)? else {
+        return Err(DataError::Format(
+            "the safetensors header isn't an object".to_owned(),
+        ));
+    };
+
+    let mut tensors = Vec::with_capacity(entries.len());
+    for (name, entry) in entries {
+        if name == "__metadata__" {
+            continue;
+        }
+
+        let (dtype, shape, offsets) = match (
+            entry.get("dtype"),
+            entry.get("shape").and_then(Json::numbers),
+            entry.get("data_offsets").and_then(Json::numbers),
+        ) {
+            (Some(Json::String(dtype)), Some(shape), Some(offsets)) if offsets.len() == 2 => {
+                (dtype.clone(), shape, offsets)
+            }
+            _ => return Err(DataError::Format(format!("tensor {:?} is malformed", name))),
+        };
+
+        let (size, element): (usize, fn(&[u8]) -> f64) = match dtype.as_str() {
+            "F64" => (8, |b| f64::from_le_bytes(b.try_into().unwrap())),
+            "F32" => (4, |b| f32::from_le_bytes(b.try_into().unwrap()) as f64),
+            // bfloat16 is the top half of an f32
+            "BF16" => (2, |b| f32::from_le_bytes([0, 0, b[0], b[1]]) as f64),
+            _ => return Err(DataError::Unsupported(format!("dtype {:?}", dtype))),
+        };
+
+        let length = checked_product(&shape)?
+            .checked_mul(size)
+            .ok_or_else(|| DataError::Format(format!("tensor {:?} is too large", name)))?;
+        let bytes = data
+            .get(offsets[0]..offsets[1])
+            .filter(|bytes| bytes.len() == length)
+            .ok_or_else(|| {
+                DataError::Format(format!("tensor {:?} doesn't match its data", name))
+            })?;
+
+        tensors.push((
+            offsets[0],
+            name,
+            NpyArray {
+                shape,
+                data: bytes.chunks_exact(size).map(element).collect(),
+            },
+        ));
+    }
+
+    tensors.sort_by_key(|&(offset, ..)| offset);
+    Ok(tensors
+        .into_iter()
+        .map(|(_, name, tensor)| (name, tensor))
+        .collect())
+}
+
+/// Loads every tensor in the safetensors file at path, see [read_safetensors]
+pub fn load_safetensors(path: impl AsRef<Path>) -> Result<Vec<(String, NpyArray)>, DataError> {
+    read_safetensors(BufReader::new(File::open(path)?))
+}
+
+/// The characters of a JSON document
+type Chars<'a> = core::iter::Peekable<core::str::Chars<'a>>;
+
+/// The parts of JSON used by safetensors headers
+#[derive(Debug)]
+enum Json {
+    /// `{...}`, in the order written
+    Object(Vec<(String, Json)>),
+    /// `[...]`
+    Array(Vec<Json>),
+    /// `"..."`
+    String(String),
+    /// A number
+    Number(f64),
+    /// `true`, `false` or `null`, which safetensors doesn't use
+    Other,
+}
+
+impl Json {
+    /// Parses a whole JSON document
+    fn parse(text: &str) -> Result<Self, DataError> {
+        let mut chars = text.trim().chars().peekable();
+        let value = Self::parse_value(&mut chars, 0)?;
+
+        match chars.next() {
+            None => Ok(value),
+            Some(c) => Err(Self::error(c)),
+        }
+    }
+
+    /// Parses the value starting at the next non-whitespace character, which is nested inside
+    /// depth arrays and objects
+    fn parse_value(chars: &mut Chars<'_>, depth: usize) -> Result<Self, DataError> {
+        Self::skip_whitespace(chars);
+        if depth >= MAX_DEPTH && matches!(chars.peek(), Some('{' | '[')) {
+            return Err(DataError::Format(format!(
+                "the safetensors header nests more than {} deep",
+                MAX_DEPTH
+            )));
+        }
+
+        match chars.next() {
+            Some('{') => {
+                let mut entries = Vec::new();
+                Self::parse_list(chars, '}', |chars| {
+                    let Json::String(key) = Self::parse_value(chars, depth + 1)? else {
+                        return Err(DataError::Format("an object key isn't a string".to_owned()));
+                    };
+                    Self::skip_whitespace(chars);
+                    match chars.next() {
+                        Some(':') => {}
+                        Some(c) => return Err(Self::error(c)),
+                        None => return Err(Self::end()),
+                    }
+                    entries.push((key, Self::parse_value(chars, depth + 1)?));
+                    Ok(())
+                })?;
+                Ok(Json::Object(entries))
+            }
+            Some('[') => {
+                let mut values = Vec::new();
+                Self::parse_list(chars, ']', |chars| {
+                    values.push(Self::parse_value(chars, depth + 1)?);
+                    Ok(())
+                })?;
+                Ok(Json::Array(values))
+            }
+            Some('"') => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => return Ok(Json::String(string)),
+                        Some('\\') => match chars.next() {
+                            Some('n') => string.push('\n'),
+                            Some('t') => string.push('\t'),
+                            Some('r') => string.push('\r'),
+                            Some('u') => {
+                                let code: String = chars.by_ref().take(4).collect();
+                                let code = u32::from_str_radix(&code, 16).map_err(|_| {
+                                    DataError::Format(format!("bad escape {}", code))
+                                })?;
+                                string.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                            }
+                            Some(c) => string.push(c),
+                            None => return Err(Self::end()),
+                        },
+                        Some(c) => string.push(c),
+                        None => return Err(Self::end()),
+                    }
+                }
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let mut number = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_digit() || "+-.eE".contains(c)) {
+                        break;
+                    }
+                    number.push(c);
+                    chars.next();
+                }
+                number
+                    .parse()
+                    .map(Json::Number)
+                    .map_err(|_| DataError::Format(format!("bad number {}", number)))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                while chars.next_if(char::is_ascii_alphabetic).is_some() {}
+                Ok(Json::Other)
+            }
+            Some(c) => Err(Self::error(c)),
+            None => Err(Self::end()),
+        }
+    }
+
+    /// Calls item for each comma separated item until close
+    fn parse_list(
+        chars: &mut Chars<'_>,
+        close: char,
+        mut item: impl FnMut(&mut Chars<'_>) -> Result<(), DataError>,
+    ) -> Result<(), DataError> {
+        Self::skip_whitespace(chars);
+        if chars.next_if_eq(&close).is_some() {
+            return Ok(());
+        }
+
+        loop {
+            item(chars)?;
+            Self::skip_whitespace(chars);
+            match chars.next() {
+                Some(',') => {}
+                Some(c) if c == close => return Ok(()),
+                Some(c) => return Err(Self::error(c)),
+                None => return Err(Self::end()),
+            }
+        }
+    }
+
+    /// Skips any whitespace
+    fn skip_whitespace(chars: &mut Chars<'_>) {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    /// The error for an unexpected character
+    fn error(c: char) -> DataError {
+        DataError::Format(format!("unexpected {:?} in the safetensors header", c))
+    }
+
+    /// The error for the header ending early
+    fn end() -> DataError {
+        DataError::Format("the safetensors header ends early".to_owned())
+    }
+
+    /// The value of key, if self is an object containing it
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// The values of an array of whole numbers
+    fn numbers(&self) -> Option<Vec<usize>> {
+        match self {
+            Json::Array(values) => values
+                .iter()
+                .map(|v| match v {
+                    Json::Number(n) if *n >= 0. && n.fract() == 0. => Some(*n as usize),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Tests
+mod test {
+    extern crate std;
+
+    #[test]
+    fn safetensors_test() {
+        use super::read_safetensors;
+        use crate::data::DataError;
+
+        let header = r#"{"__metadata__": {"format": "pt"},
+            "fc.weight": {"dtype": "F32", "shape": [2, 3], "data_offsets": [8, 32]},
+            "fc.bias": {"dtype": "BF16", "shape": [2], "data_offsets": [4, 8]},
+            "scale": {"dtype": "F64", "shape": [], "data_offsets": [32, 40]},
+            "empty": {"dtype": "F32", "shape": [0], "data_offsets": [0, 0]}}"#;
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend_from_slice(header.as_bytes());
+        file.extend_from_slice(&[0; 4]);
+        file.extend_from_slice(&(-1.5f32).to_le_bytes()[2..]);
+        file.extend_from_slice(&(0.25f32).to_le_bytes()[2..]);
+        for v in [1f32, 2., 3., 4., 5., 6.] {
+            file.extend_from_slice(&v.to_le_bytes());
+        }
+        file.extend_from_slice(&0.1f64.to_le_bytes());
+
+        let tensors = read_safetensors(file.as_slice()).unwrap();
+        let names: std::vec::Vec<_> = tensors.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["empty", "fc.bias", "fc.weight", "scale"]);
+        assert_eq!(tensors[1].1.data, [-1.5, 0.25]);
+        assert_eq!(tensors[2].1.shape, [2, 3]);
+        assert_eq!(tensors[2].1.data, [1., 2., 3., 4., 5., 6.]);
+        assert_eq!(tensors[3].1.data, [0.1]);
+
+        // the offsets run past the data
+        let header = r#"{"x": {"dtype": "F32", "shape": [2], "data_offsets": [0, 8]}}"#;
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend_from_slice(header.as_bytes());
+        file.extend_from_slice(&[0; 4]);
+        assert!(matches!(
+            read_safetensors(file.as_slice()),
+            Err(DataError::Format(_))
+        ));
+
+        // a header length that can't be allocated
+        let file = u64::MAX.to_le_bytes();
+        assert!(matches!(
+            read_safetensors(file.as_slice()),
+            Err(DataError::Format(_))
+        ));
+
+        // a shape whose size overflows
+        let header =
+            r#"{"x": {"dtype": "F64", "shape": [4294967296, 4294967296], "data_offsets": [0, 0]}}"#;
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend_from_slice(header.as_bytes());
+        assert!(matches!(
+            read_safetensors(file.as_slice()),
+            Err(DataError::Format(_))
+        ));
+
+        // nesting deep enough to overflow the stack
+        let header = "[".repeat(100_000);
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend_from_slice(header.as_bytes());
+        assert!(matches!(
+            read_safetensors(file.as_slice()),
+            Err(DataError::Format(_))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use core::fmt::{Display, Formatter, Result};
+
+/// Describes a single layer of a network
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayerInfo {
+    /// The name of the layer, e.g. the field name in a `network!`
+    pub name: &'static str,
+    /// What kind of layer it is, e.g. `Layer` or `Softmax`
+    pub kind: &'static str,
+    /// How many values go into the layer
+    pub inputs: usize,
+    /// How many values come out of the layer
+    pub outputs: usize,
+    /// How many numbers are stored in the layer
+    pub parameters: usize,
+    /// How many of those parameters are changed by training
+    pub trainable_parameters: usize,
+}
+
+impl LayerInfo {
+    /// Describes an operation, which has the same number of inputs and outputs and by default no
+    /// parameters
+    pub(crate) const fn operation(name: &'static str, kind: &'static str, width: usize) -> Self {
+        Self {
+            name,
+            kind,
+            inputs: width,
+            outputs: width,
+            parameters: 0,
+            trainable_parameters: 0,
+        }
+    }
+}
+
+/// Describes the layers of a network with `INPUTS` inputs and `OUTPUTS` outputs, e.g. to print a
+/// summary of it. As operations work on any width, the widths are generic here too.
+/// ## Example
+/// ```rust
+///     use neural_thingamajigy::{network, NetworkInfo, ChainableNetwork, RandomisableNetwork, operations::Softmax};
+///     use rand::rngs::OsRng;
+///
+///     network!(pub Classifier, f32, 4, hidden: 8, logits: 3);
+///
+///     let classifier = Classifier::random(&mut OsRng).chain(Softmax);
+///     assert_eq!(classifier.widths().collect::<Vec<_>>(), [4, 8, 3, 3]);
+///     assert_eq!(classifier.parameter_count(), 4 * 8 + 8 + 8 * 3 + 3);
+///
+///     println!("{}", classifier.summary());
+/// ```
+pub trait NetworkInfo<const INPUTS: usize, const OUTPUTS: usize> {
+    /// Describes each layer, in the order they are evaluated
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo>;
+
+    /// How many layers there are
+    fn layer_count(&self) -> usize {
+        self.layer_info().count()
+    }
+
+    /// The width of the inputs, followed by the width of the outputs of each layer
+    fn widths(&self) -> impl Iterator<Item = usize> {
+        core::iter::once(INPUTS).chain(self.layer_info().map(|layer| layer.outputs))
+    }
+
+    /// How many numbers are stored in the network
+    fn parameter_count(&self) -> usize {
+        self.layer_info().map(|layer| layer.parameters).sum()
+    }
+
+    /// How many numbers are changed by training the network
+    fn trainable_parameter_count(&self) -> usize {
+        self.layer_info()
+            .map(|layer| layer.trainable_parameters)
+            .sum()
+    }
+
+    /// A table of every layer and their parameters, like Keras' `model.summary()`, which is
+    /// printed using [Display]
+    fn summary(&self) -> Summary<'_, Self, INPUTS, OUTPUTS> {
+        Summary(self)
+    }
+}
+
+/// A table of the layers in a network, made by [NetworkInfo::summary]
+pub struct Summary<'a, N: ?Sized, const INPUTS: usize, const OUTPUTS: usize>(&'a N);
+
+impl<N: NetworkInfo<INPUTS, OUTPUTS> + ?Sized, const INPUTS: usize, const OUTPUTS: usize> Display
+    for Summary<'_, N, INPUTS, OUTPUTS>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(
+            f,
+            "{:<20}{:<16}{:>8}{:>8}{:>12}",
+            "Layer", "Type", "Inputs", "Outputs", "Parameters"
+        )?;
+        for layer in self.0.layer_info() {
+            writeln!(
+                f,
+                "{:<20}{:<16}{:>8}{:>8}{:>12}",
+                layer.name, layer.kind, layer.inputs, layer.outputs, layer.parameters
+            )?;
+        }
+
+        let total = self.0.parameter_count();
+        let trainable = self.0.trainable_parameter_count();
+        writeln!(f, "Total parameters: {total}")?;
+        writeln!(f, "Trainable parameters: {trainable}")?;
+        write!(f, "Non-trainable parameters: {}", total - trainable)
+    }
+}
+
+impl<const INPUTS: usize, const OUTPUTS: usize, N: NetworkInfo<INPUTS, OUTPUTS>>
+    NetworkInfo<INPUTS, OUTPUTS> for &N
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        (*self).layer_info()
+    }
+}
+
+impl<const INPUTS: usize, const OUTPUTS: usize, N: NetworkInfo<INPUTS, OUTPUTS>>
+    NetworkInfo<INPUTS, OUTPUTS> for &mut N
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        (**self).layer_info()
+    }
+}
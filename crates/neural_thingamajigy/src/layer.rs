@@ -1,22 +1,33 @@
 /// This defines the LayerData type, which contains data used in training about a layer.
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 mod layer_data;
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 pub use layer_data::LayerGradient;
 
 /// Contains everything relating to training a layer.
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 mod layer_training;
 
+/// Contains building a layer from its weights and biases, and reading them.
+mod layer_parts;
+pub use layer_parts::{ShapeError, WeightLayout};
+
+/// Contains masking and sparse conversion of pruned layers.
+#[cfg(feature = "std-train")]
+mod layer_pruning;
+
 use nalgebra::{RealField, SMatrix, SVector};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::activators::Activator;
+use crate::{activators::Activator, LayerInfo};
+#[cfg(feature = "random")]
+use rand::{distributions::Standard, prelude::Distribution, Rng};
 
 /// A layer of neurons in the network, this contains the weights, biases, activaiton function and it's gradient.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Layer<T: RealField, const INPUTS: usize, const OUTPUTS: usize> {
     /// A matrix representing the weights of each value for each neuron.
     weight: SMatrix<T, OUTPUTS, INPUTS>,
@@ -38,4 +49,56 @@ impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Layer<T, IN
 
         activated + self.bias
     }
+
+    /// Like [Self::through], but for every column of inputs at once
+    pub fn through_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, OUTPUTS, B> {
+        let mut outputs = self.weight * inputs;
+        outputs.apply(|v| *v = activator.activation(*v));
+        for mut column in outputs.column_iter_mut() {
+            column += self.bias;
+        }
+
+        outputs
+    }
+
+    /// Converts every weight and bias to another number type, going through f64(which is exact
+    /// between f32 and f64)
+    pub fn cast<U: RealField + Copy>(&self) -> Layer<U, INPUTS, OUTPUTS> {
+        let cast = |v: T| nalgebra::convert::<f64, U>(nalgebra::convert_unchecked::<T, f64>(v));
+
+        Layer {
+            weight: self.weight.map(cast),
+            bias: self.bias.map(cast),
+        }
+    }
+
+    /// Describes this layer, which is called name in its network
+    pub fn info(&self, name: &'static str) -> LayerInfo {
+        let parameters = INPUTS * OUTPUTS + OUTPUTS;
+
+        LayerInfo {
+            name,
+            kind: "Layer",
+            inputs: INPUTS,
+            outputs: OUTPUTS,
+            parameters,
+            trainable_parameters: parameters,
+        }
+    }
+
+    /// Generates a new layer with all values set using the Standard distribution
+    #[cfg(feature = "random")]
+    pub fn random(rng: &mut impl Rng) -> Self
+    where
+        Standard: Distribution<T>,
+    {
+        Self {
+            weight: SMatrix::from_iterator(rng.sample_iter(Standard)),
+            bias: SMatrix::from_iterator(rng.sample_iter(Standard)),
+        }
+    }
 }
@@ -1,10 +1,12 @@
-extern crate std;
-
 use crate::valueset::ValueSet;
 use nalgebra::{RealField, SMatrix, SVector};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Data about a layer generated via backpropogation used in training.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LayerGradient<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> {
     /// The gradient of the weight values with respect to the loss function.
     pub weight_gradient: SMatrix<T, OUTPUTS, INPUTS>,
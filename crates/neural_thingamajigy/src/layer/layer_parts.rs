@@ -0,0 +1,151 @@
+use super::Layer;
+use core::{
+    error::Error,
+    fmt::{self, Display},
+};
+use nalgebra::{RealField, SMatrix, SVector};
+
+/// How the weights of a layer are laid out in a flat, row-major array exported by another
+/// framework
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeightLayout {
+    /// `[OUTPUTS, INPUTS]`, e.g. PyTorch's `nn.Linear.weight`, or the B of an ONNX `Gemm` with
+    /// `transB = 1`
+    OutputsByInputs,
+    /// `[INPUTS, OUTPUTS]`, e.g. a Keras `Dense` kernel, or the weights of an ONNX `MatMul`
+    InputsByOutputs,
+}
+
+/// The weights or biases given to a layer weren't the length of the layer's
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShapeError {
+    /// What was the wrong length, `weight` or `bias`
+    pub part: &'static str,
+    /// The length required
+    pub expected: usize,
+    /// The length found
+    pub found: usize,
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} values for the {}, but found {}",
+            self.expected, self.part, self.found
+        )
+    }
+}
+
+impl Error for ShapeError {}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Layer<T, INPUTS, OUTPUTS> {
+    /// Creates a layer with the given weights(a row for each output) and biases
+    pub fn from_parts(weight: SMatrix<T, OUTPUTS, INPUTS>, bias: SVector<T, OUTPUTS>) -> Self {
+        Self { weight, bias }
+    }
+
+    /// Creates a layer from arrays exported by another framework, with `INPUTS * OUTPUTS`
+    /// weights laid out as layout, and `OUTPUTS` biases.
+    ///
+    /// Note that a layer adds its bias after the activation, whereas most frameworks add it
+    /// before, so an imported layer only gives the same outputs if it has no bias, its
+    /// activation is linear, or it is the last layer and that framework's activation is applied
+    /// to its outputs afterwards.
+    pub fn from_slices(weight: &[T], bias: &[T], layout: WeightLayout) -> Result<Self, ShapeError> {
+        if weight.len() != INPUTS * OUTPUTS {
+            return Err(ShapeError {
+                part: "weight",
+                expected: INPUTS * OUTPUTS,
+                found: weight.len(),
+            });
+        }
+        if bias.len() != OUTPUTS {
+            return Err(ShapeError {
+                part: "bias",
+                expected: OUTPUTS,
+                found: bias.len(),
+            });
+        }
+
+        // nalgebra is column-major, so [INPUTS, OUTPUTS] row-major is already a column per input
+        let weight = match layout {
+            WeightLayout::OutputsByInputs => SMatrix::from_row_slice(weight),
+            WeightLayout::InputsByOutputs => SMatrix::from_column_slice(weight),
+        };
+
+        Ok(Self::from_parts(weight, SVector::from_column_slice(bias)))
+    }
+
+    /// The weights, with a row for each output
+    pub fn weights(&self) -> &SMatrix<T, OUTPUTS, INPUTS> {
+        &self.weight
+    }
+
+    /// Like [Self::weights], but mutable
+    pub fn weights_mut(&mut self) -> &mut SMatrix<T, OUTPUTS, INPUTS> {
+        &mut self.weight
+    }
+
+    /// The bias of each output
+    pub fn bias(&self) -> &SVector<T, OUTPUTS> {
+        &self.bias
+    }
+
+    /// Like [Self::bias], but mutable
+    pub fn bias_mut(&mut self) -> &mut SVector<T, OUTPUTS> {
+        &mut self.bias
+    }
+}
+
+/// Tests
+mod test {
+    #[test]
+    fn parts_test() {
+        use super::{ShapeError, WeightLayout};
+        use crate::{activators::Linear, Layer};
+        use nalgebra::{Matrix2x3, Vector2, Vector3};
+
+        // [[1, 2, 3], [4, 5, 6]] as exported by PyTorch and Keras
+        let torch = [1., 2., 3., 4., 5., 6.];
+        let keras = [1., 4., 2., 5., 3., 6.];
+        let bias = [0.5, -0.5];
+
+        let layer = Layer::<f64, 3, 2>::from_parts(
+            Matrix2x3::new(1., 2., 3., 4., 5., 6.),
+            Vector2::new(0.5, -0.5),
+        );
+        let mut torch_layer =
+            Layer::<f64, 3, 2>::from_slices(&torch, &bias, WeightLayout::OutputsByInputs).unwrap();
+        let keras_layer =
+            Layer::<f64, 3, 2>::from_slices(&keras, &bias, WeightLayout::InputsByOutputs).unwrap();
+        assert_eq!(torch_layer, layer);
+        assert_eq!(keras_layer, layer);
+        assert_eq!(
+            layer.through(Vector3::new(1., 0., -1.), &Linear),
+            Vector2::new(-1.5, -2.5)
+        );
+
+        torch_layer.weights_mut()[(1, 2)] = 0.;
+        torch_layer.bias_mut()[0] = 1.;
+        assert_eq!(torch_layer.weights()[(1, 2)], 0.);
+        assert_eq!(torch_layer.bias(), &Vector2::new(1., -0.5));
+
+        assert_eq!(
+            Layer::<f64, 3, 2>::from_slices(&torch[1..], &bias, WeightLayout::OutputsByInputs),
+            Err(ShapeError {
+                part: "weight",
+                expected: 6,
+                found: 5
+            })
+        );
+        assert_eq!(
+            Layer::<f64, 3, 2>::from_slices(&torch, &[], WeightLayout::InputsByOutputs),
+            Err(ShapeError {
+                part: "bias",
+                expected: 2,
+                found: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,66 @@
+extern crate std;
+
+use super::{Layer, LayerGradient};
+use crate::sparse::SparseLayerBuf;
+use nalgebra::{RealField, SVector};
+use std::vec::Vec;
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Layer<T, INPUTS, OUTPUTS> {
+    /// Calls f with every weight
+    pub fn inspect_weights(&self, f: &mut impl FnMut(&T)) {
+        self.weight.iter().for_each(f);
+    }
+
+    /// Replaces every weight with f(weight), in the same order as [Self::inspect_weights]
+    pub fn map_weights(&mut self, f: &mut impl FnMut(&T) -> T) {
+        self.weight.apply(|w| *w = f(w));
+    }
+
+    /// A gradient shaped mask, 1 for every bias and non-zero weight, and 0 for every pruned(zero)
+    /// weight. Multiplying a nudge by this keeps the pruned weights at zero.
+    pub fn mask(&self) -> LayerGradient<T, INPUTS, OUTPUTS> {
+        LayerGradient {
+            weight_gradient: self
+                .weight
+                .map(|w| if w == T::zero() { T::zero() } else { T::one() }),
+            bias_gradient: SVector::repeat(T::one()),
+        }
+    }
+
+    /// Converts the layer into compressed sparse rows, leaving out the zero weights
+    pub fn to_sparse(&self) -> SparseLayerBuf<T, INPUTS, OUTPUTS> {
+        const { Self::check_sparse_width() };
+
+        let mut sparse = SparseLayerBuf {
+            row_starts: Vec::with_capacity(OUTPUTS + 1),
+            columns: Vec::new(),
+            values: Vec::new(),
+            bias: self.bias.iter().copied().collect(),
+        };
+
+        for row in self.weight.row_iter() {
+            sparse.row_starts.push(sparse.values.len() as u32);
+            for (column, &value) in row.iter().enumerate() {
+                if value != T::zero() {
+                    sparse.columns.push(column as u16);
+                    sparse.values.push(value);
+                }
+            }
+        }
+        sparse.row_starts.push(sparse.values.len() as u32);
+
+        sparse
+    }
+
+    /// Panics at compile time if the columns or row starts of a sparse layer could overflow
+    const fn check_sparse_width() {
+        assert!(
+            INPUTS <= u16::MAX as usize + 1,
+            "sparse layers can't have more than 65536 inputs"
+        );
+        assert!(
+            INPUTS * OUTPUTS <= u32::MAX as usize,
+            "sparse layers can't have more than u32::MAX weights"
+        );
+    }
+}
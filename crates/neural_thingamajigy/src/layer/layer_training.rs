@@ -1,7 +1,6 @@
 use super::{Layer, LayerGradient};
 use crate::activators::Activator;
 use nalgebra::{RealField, SMatrix, SVector};
-use rand::{distributions::Standard, prelude::Distribution, Rng};
 
 impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Layer<T, INPUTS, OUTPUTS> {
     /// The gradient of the layer for a given set of inputs.
@@ -10,7 +9,28 @@ impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Layer<T, IN
         inputs: SVector<T, INPUTS>,
         activator: &impl Activator<T>,
     ) -> SMatrix<T, OUTPUTS, INPUTS> {
-        activator.activation_gradient_matrix(self.weight * inputs) * self.weight
+        // scaling each row by its activation gradient, rather than multiplying by a diagonal matrix
+        let activation_gradients = (self.weight * inputs).map(|v| activator.activation_gradient(v));
+        let mut gradient = self.weight;
+        for (mut row, activation_gradient) in
+            gradient.row_iter_mut().zip(activation_gradients.iter())
+        {
+            row *= *activation_gradient;
+        }
+
+        gradient
+    }
+
+    /// The loss gradients before activation, applying the activation gradient element-wise
+    fn deltas(
+        &self,
+        loss_gradients: SVector<T, OUTPUTS>,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, OUTPUTS> {
+        (self.weight * inputs)
+            .map(|v| activator.activation_gradient(v))
+            .component_mul(&loss_gradients)
     }
 
     /// Takes a set of inputs and loss gradients(with respect to the outputs) and calculates LayerData based on them.
@@ -24,20 +44,66 @@ impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Layer<T, IN
         let bias_gradient = loss_gradients;
 
         // each weight is shifted by it's pre-activation loss gradient multiplied by it's input
-        let weight_gradient = activator.activation_gradient_matrix(self.weight * inputs)
-            * loss_gradients
-            * inputs.transpose();
+        let deltas = self.deltas(loss_gradients, inputs, activator);
+        let weight_gradient = deltas * inputs.transpose();
 
-        let gradient = self.gradient(inputs, activator);
         (
             LayerGradient {
                 weight_gradient,
                 bias_gradient,
             },
-            gradient.transpose() * loss_gradients,
+            self.weight.tr_mul(&deltas),
         )
     }
 
+    /// Like [Self::backpropogate], but only calculates the loss gradients of the inputs
+    pub fn backpropogate_inputs(
+        &self,
+        loss_gradients: SVector<T, OUTPUTS>,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.weight
+            .tr_mul(&self.deltas(loss_gradients, inputs, activator))
+    }
+
+    /// Like [Self::backpropogate], but for every column of a batch at once, summing the gradient
+    /// over the batch. The activation gradient is applied element-wise, and the weight gradient
+    /// is a single matrix-matrix multiplication.
+    pub fn backpropogate_batch<const B: usize>(
+        &self,
+        loss_gradients: SMatrix<T, OUTPUTS, B>,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (LayerGradient<T, INPUTS, OUTPUTS>, SMatrix<T, INPUTS, B>) {
+        // the loss gradient before activation
+        let deltas = (self.weight * inputs).zip_map(&loss_gradients, |weighted, loss| {
+            activator.activation_gradient(weighted) * loss
+        });
+
+        (
+            LayerGradient {
+                weight_gradient: deltas * inputs.transpose(),
+                bias_gradient: loss_gradients.column_sum(),
+            },
+            self.weight.tr_mul(&deltas),
+        )
+    }
+
+    /// Like [Self::backpropogate_batch], but only calculates the loss gradients of the inputs
+    pub fn backpropogate_inputs_batch<const B: usize>(
+        &self,
+        loss_gradients: SMatrix<T, OUTPUTS, B>,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        let deltas = (self.weight * inputs).zip_map(&loss_gradients, |weighted, loss| {
+            activator.activation_gradient(weighted) * loss
+        });
+
+        self.weight.tr_mul(&deltas)
+    }
+
     /// Applies weight and bias shifts, normalized and multiplied by the learning rate.
     pub fn apply_shifts(
         &mut self,
@@ -47,15 +113,4 @@ impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Layer<T, IN
         self.weight += weight_direction;
         self.bias += bias_direction;
     }
-
-    /// Generates a new layer with all values set using the Standard distribution
-    pub fn random(rng: &mut impl Rng) -> Self
-    where
-        Standard: Distribution<T>,
-    {
-        Self {
-            weight: SMatrix::from_iterator(rng.sample_iter(Standard)),
-            bias: SMatrix::from_iterator(rng.sample_iter(Standard)),
-        }
-    }
 }
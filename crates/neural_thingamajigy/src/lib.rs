@@ -4,21 +4,57 @@
 
 /// Defines the Activator type
 pub mod activators;
+/// Defines autoencoders, which learn to reconstruct their inputs
+pub mod autoencoder;
+/// Computes gradients and takes training steps without allocating, so networks can be trained
+/// without std
+#[cfg(feature = "backprop")]
+mod backprop;
 /// Defines the ChainedNetwork type and chain, supporting joining networks together
 mod chain;
+/// Loads datasets from common file formats
+#[cfg(feature = "std-train")]
+pub mod data;
+/// Defines the Embedding type, which looks up a learned vector for each token
+mod embedding;
+/// Defines Frozen, which stops a network from being trained
+mod frozen;
+/// Imports networks trained in other frameworks, from safetensors state-dicts and ONNX models
+#[cfg(feature = "std-train")]
+pub mod import;
+/// Defines the NetworkInfo trait, which describes the layers of a network
+mod info;
 /// This defines the Layer type, representing a layer of neurons and handles weighting, activation and biases.
 mod layer;
 /// This defines a network type, containing a sequence of layers.
 mod network;
 /// Defines common operations for [pre/post]processing
 pub mod operations;
+/// Samples the random values used while training
+#[cfg(feature = "random")]
+mod random;
+/// Defines SparseLayer, a layer of pruned weights that skips the zeros
+mod sparse;
 /// This holds the train function, allowing users to train their networks via MSE.
-#[cfg(feature = "train")]
+#[cfg(feature = "std-train")]
 mod train;
+/// Defines attention and transformer encoder blocks for fixed-length sequences
+pub mod transformer;
 /// Defines the ValueSet trait, which abstracts over anything which is a nested collection of a value
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 pub mod valueset;
 
-pub use {chain::ChainableNetwork, layer::Layer, network::*, network_macro::network};
-#[cfg(feature = "train")]
-pub use {train::*, valueset::ValueSet};
+#[cfg(feature = "backprop")]
+pub use {backprop::*, embedding::EmbeddingGradient, valueset::ValueSet};
+pub use {
+    chain::{ChainableNetwork, ChainedNetwork},
+    embedding::Embedding,
+    frozen::Frozen,
+    info::{LayerInfo, NetworkInfo, Summary},
+    layer::{Layer, ShapeError, WeightLayout},
+    network::*,
+    network_macro::network,
+    sparse::SparseLayer,
+};
+#[cfg(feature = "std-train")]
+pub use {sparse::SparseLayerBuf, train::*};
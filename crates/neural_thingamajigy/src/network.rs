@@ -1,10 +1,11 @@
 use crate::activators::Activator;
-use nalgebra::{RealField, SVector};
-#[cfg(feature = "train")]
-use {
-    crate::valueset::ValueSet,
-    rand::{distributions::Standard, prelude::Distribution, Rng},
-};
+#[cfg(feature = "backprop")]
+use crate::valueset::ValueSet;
+#[cfg(feature = "std-train")]
+use nalgebra::DMatrix;
+use nalgebra::{RealField, SMatrix, SVector};
+#[cfg(feature = "random")]
+use rand::{distributions::Standard, prelude::Distribution, Rng};
 
 /// Represents a neural network
 pub trait Network<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> {
@@ -14,8 +15,59 @@ pub trait Network<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize
         inputs: SVector<T, INPUTS>,
         activator: &impl Activator<T>,
     ) -> SVector<T, OUTPUTS>;
+
+    /// Evaluate the network with every column of inputs, returning the outputs as columns. By
+    /// default each column is evaluated separately, but networks of layers use matrix-matrix
+    /// multiplication, which is much faster for large batches.
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, OUTPUTS, B> {
+        let mut outputs = SMatrix::<T, OUTPUTS, B>::zeros();
+        for (i, column) in inputs.column_iter().enumerate() {
+            outputs.set_column(i, &self.evaluate(column.into_owned(), activator));
+        }
+
+        outputs
+    }
+
+    /// Evaluate the network with every column of inputs, which must have `INPUTS` rows, in
+    /// batches of [DYNAMIC_BATCH_SIZE] columns using [Self::evaluate_batch]
+    /// # Panics
+    /// If inputs doesn't have `INPUTS` rows
+    #[cfg(feature = "std-train")]
+    fn evaluate_dynamic_batch(
+        &self,
+        inputs: &DMatrix<T>,
+        activator: &impl Activator<T>,
+    ) -> DMatrix<T> {
+        assert_eq!(inputs.nrows(), INPUTS, "inputs must have INPUTS rows");
+
+        let mut outputs = DMatrix::zeros(OUTPUTS, inputs.ncols());
+        for start in (0..inputs.ncols()).step_by(DYNAMIC_BATCH_SIZE) {
+            let width = DYNAMIC_BATCH_SIZE.min(inputs.ncols() - start);
+
+            // the last batch is padded with zeros, whose outputs are thrown away
+            let mut batch = SMatrix::<T, INPUTS, DYNAMIC_BATCH_SIZE>::zeros();
+            batch
+                .columns_mut(0, width)
+                .copy_from(&inputs.columns(start, width));
+
+            let batch = self.evaluate_batch(batch, activator);
+            outputs
+                .columns_mut(start, width)
+                .copy_from(&batch.columns(0, width));
+        }
+
+        outputs
+    }
 }
 
+/// How many columns [Network::evaluate_dynamic_batch] evaluates at once
+#[cfg(feature = "std-train")]
+pub const DYNAMIC_BATCH_SIZE: usize = 64;
+
 impl<
         T: RealField + Copy,
         const INPUTS: usize,
@@ -30,6 +82,14 @@ impl<
     ) -> SVector<T, OUTPUTS> {
         (*self).evaluate(inputs, activator)
     }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, OUTPUTS, B> {
+        (*self).evaluate_batch(inputs, activator)
+    }
 }
 
 impl<
@@ -46,10 +106,18 @@ impl<
     ) -> SVector<T, OUTPUTS> {
         (**self).evaluate(inputs, activator)
     }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, OUTPUTS, B> {
+        (**self).evaluate_batch(inputs, activator)
+    }
 }
 
 /// Represents a network that exposes training functionality
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 pub trait TrainableNetwork<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> {
     /// The type representing the stored state of the network when given a particular inputs
     type LayerInputs;
@@ -71,11 +139,23 @@ pub trait TrainableNetwork<T: RealField + Copy, const INPUTS: usize, const OUTPU
         activator: &impl Activator<T>,
     ) -> (Self::Gradient, SVector<T, INPUTS>);
 
+    /// Like [Self::get_gradient], but only returns the gradient of the inputs, so networks can
+    /// skip calculating their own gradient, e.g. when they're [crate::Frozen]
+    fn get_input_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, OUTPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        self.get_gradient(layer_inputs, output_loss_gradients, activator)
+            .1
+    }
+
     /// Applies a nudge to the network
     fn apply_nudge(&mut self, nudge: Self::Gradient);
 }
 
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 impl<
         T: RealField + Copy,
         const INPUTS: usize,
@@ -104,13 +184,60 @@ impl<
         (**self).get_gradient(layer_inputs, output_loss_gradients, activator)
     }
 
+    fn get_input_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, OUTPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        (**self).get_input_gradient(layer_inputs, output_loss_gradients, activator)
+    }
+
     fn apply_nudge(&mut self, nudge: Self::Gradient) {
         (**self).apply_nudge(nudge);
     }
 }
 
+/// A [TrainableNetwork] that can also be trained on a batch of samples(as the columns of a
+/// matrix) at once, which is much faster for networks of layers. This is implemented by
+/// `network!`, and used by [crate::train_batched].
+#[cfg(feature = "backprop")]
+pub trait BatchTrainableNetwork<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>:
+    TrainableNetwork<T, INPUTS, OUTPUTS>
+{
+    /// The stored state of the network for a batch of `B` inputs
+    type BatchLayerInputs<const B: usize>;
+
+    /// Like [TrainableNetwork::evaluate_training], for every column of inputs
+    fn evaluate_training_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (SMatrix<T, OUTPUTS, B>, Self::BatchLayerInputs<B>);
+
+    /// Like [TrainableNetwork::get_gradient], for every column of output_loss_gradients. The
+    /// gradient is summed over the batch.
+    fn get_gradient_batch<const B: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<B>,
+        output_loss_gradients: SMatrix<T, OUTPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SMatrix<T, INPUTS, B>);
+
+    /// Like [TrainableNetwork::get_input_gradient], for every column of output_loss_gradients
+    fn get_input_gradient_batch<const B: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<B>,
+        output_loss_gradients: SMatrix<T, OUTPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        self.get_gradient_batch(layer_inputs, output_loss_gradients, activator)
+            .1
+    }
+}
+
 /// Represents a network that can be randomised
-#[cfg(feature = "train")]
+#[cfg(feature = "random")]
 pub trait RandomisableNetwork<T>
 where
     Standard: Distribution<T>,
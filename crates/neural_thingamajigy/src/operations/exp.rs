@@ -1,6 +1,6 @@
-use nalgebra::{RealField, SVector};
+use nalgebra::{RealField, SMatrix, SVector};
 
-use crate::Network;
+use crate::{LayerInfo, Network, NetworkInfo};
 
 /// Performs e^x for each value in the supplied vector
 pub struct Exp;
@@ -13,12 +13,26 @@ impl<T: RealField + Copy, const INPUTS: usize> Network<T, INPUTS, INPUTS> for Ex
     ) -> SVector<T, INPUTS> {
         inputs.map(|i| i.exp())
     }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        _: &impl crate::activators::Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        inputs.map(|i| i.exp())
+    }
+}
+
+impl<const INPUTS: usize> NetworkInfo<INPUTS, INPUTS> for Exp {
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        core::iter::once(LayerInfo::operation("exp", "Exp", INPUTS))
+    }
 }
 
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 use crate::TrainableNetwork;
 
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 impl<T: RealField + Copy, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUTS> for Exp {
     type LayerInputs = SVector<T, INPUTS>;
 
@@ -62,9 +76,9 @@ mod test {
 
         assert!(expected - E.powf(num) < 0.01);
 
-        assert_eq!(expected, Exp.evaluate(input.clone(), &Sigmoid).x); // Sigmoid is arbitrary and shouldn't do anything
+        assert_eq!(expected, Exp.evaluate(input, &Sigmoid).x); // Sigmoid is arbitrary and shouldn't do anything
 
-        let (answer, data) = Exp.evaluate_training(input.clone(), &Sigmoid);
+        let (answer, data) = Exp.evaluate_training(input, &Sigmoid);
         assert_eq!(expected, answer.x);
 
         let (_, grad) = Exp.get_gradient(&data, Vector1::new(1f32), &Sigmoid);
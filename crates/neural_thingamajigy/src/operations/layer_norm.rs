@@ -0,0 +1,197 @@
+use nalgebra::{RealField, SVector};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{LayerInfo, Network, NetworkInfo};
+
+/// Normalizes each token of a sequence to have a mean of 0 and a variance of 1, then scales and
+/// shifts it by a learned gain and bias. The inputs are split into tokens of `DIM` values, so
+/// `INPUTS` must be a multiple of `DIM`, and a single vector is just `INPUTS == DIM`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerNorm<T: RealField, const DIM: usize> {
+    /// What each normalized value is multiplied by
+    pub gain: SVector<T, DIM>,
+    /// What is added to each normalized value after the gain
+    pub bias: SVector<T, DIM>,
+    /// Added to the variance to avoid dividing by 0
+    pub epsilon: T,
+}
+
+impl<T: RealField + Copy, const DIM: usize> LayerNorm<T, DIM> {
+    /// Creates a LayerNorm which only normalizes, with a gain of 1 and a bias of 0
+    pub fn new() -> Self {
+        Self {
+            gain: SVector::repeat(T::one()),
+            bias: SVector::zeros(),
+            epsilon: nalgebra::convert(1e-5),
+        }
+    }
+
+    /// Returns the normalized token and the reciprocal of its standard deviation
+    fn normalize_token(&self, token: &[T]) -> (SVector<T, DIM>, T) {
+        let token = SVector::<T, DIM>::from_column_slice(token);
+        let count: T = nalgebra::convert(DIM as f64);
+
+        let mean = token.sum() / count;
+        let centred = token.add_scalar(-mean);
+        let inverse_deviation = T::one() / (centred.norm_squared() / count + self.epsilon).sqrt();
+
+        (centred * inverse_deviation, inverse_deviation)
+    }
+
+    /// Panics at compile time if INPUTS isn't made of whole tokens
+    const fn check_width<const INPUTS: usize>() {
+        assert!(
+            DIM > 0 && INPUTS.is_multiple_of(DIM),
+            "LayerNorm inputs must be a multiple of DIM"
+        );
+    }
+}
+
+impl<T: RealField + Copy, const DIM: usize> Default for LayerNorm<T, DIM> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RealField + Copy, const DIM: usize, const INPUTS: usize> Network<T, INPUTS, INPUTS>
+    for LayerNorm<T, DIM>
+{
+    fn evaluate(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        _: &impl crate::activators::Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        const { Self::check_width::<INPUTS>() };
+
+        let mut outputs = inputs;
+        for token in outputs.as_mut_slice().chunks_mut(DIM) {
+            let (normalized, _) = self.normalize_token(token);
+            let scaled = normalized.component_mul(&self.gain) + self.bias;
+            token.copy_from_slice(scaled.as_slice());
+        }
+
+        outputs
+    }
+}
+
+impl<T: RealField + Copy, const DIM: usize, const INPUTS: usize> NetworkInfo<INPUTS, INPUTS>
+    for LayerNorm<T, DIM>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        core::iter::once(LayerInfo {
+            parameters: 2 * DIM,
+            trainable_parameters: 2 * DIM,
+            ..LayerInfo::operation("layer_norm", "LayerNorm", INPUTS)
+        })
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::{LayerGradient, TrainableNetwork};
+#[cfg(feature = "random")]
+use {
+    crate::RandomisableNetwork,
+    rand::{distributions::Standard, prelude::Distribution, Rng},
+};
+
+#[cfg(feature = "backprop")]
+impl<T: RealField + Copy, const DIM: usize, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUTS>
+    for LayerNorm<T, DIM>
+{
+    // the statistics are cheap to recalculate, so only the inputs are kept
+    type LayerInputs = SVector<T, INPUTS>;
+
+    // the gain is a weight with a single input
+    type Gradient = LayerGradient<T, 1, DIM>;
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl crate::activators::Activator<T>,
+    ) -> (SVector<T, INPUTS>, Self::LayerInputs) {
+        (self.evaluate(inputs, activator), inputs)
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, INPUTS>,
+        _: &impl crate::activators::Activator<T>,
+    ) -> (Self::Gradient, SVector<T, INPUTS>) {
+        let count: T = nalgebra::convert(DIM as f64);
+        let mut gain_gradient = SVector::zeros();
+        let mut bias_gradient = SVector::zeros();
+        let mut input_gradients = SVector::<T, INPUTS>::zeros();
+
+        let tokens = layer_inputs
+            .as_slice()
+            .chunks(DIM)
+            .zip(output_loss_gradients.as_slice().chunks(DIM))
+            .zip(input_gradients.as_mut_slice().chunks_mut(DIM));
+
+        for ((token, loss_gradients), input_gradient) in tokens {
+            let (normalized, inverse_deviation) = self.normalize_token(token);
+            let loss_gradients = SVector::<T, DIM>::from_column_slice(loss_gradients);
+
+            gain_gradient += loss_gradients.component_mul(&normalized);
+            bias_gradient += loss_gradients;
+
+            // the mean and variance depend on every value, which removes their components from the gradient
+            let normalized_gradient = loss_gradients.component_mul(&self.gain);
+            let gradient = (normalized_gradient.add_scalar(-normalized_gradient.sum() / count)
+                - normalized * (normalized_gradient.dot(&normalized) / count))
+                * inverse_deviation;
+            input_gradient.copy_from_slice(gradient.as_slice());
+        }
+
+        (
+            LayerGradient {
+                weight_gradient: gain_gradient,
+                bias_gradient,
+            },
+            input_gradients,
+        )
+    }
+
+    fn apply_nudge(&mut self, nudge: Self::Gradient) {
+        self.gain += nudge.weight_gradient;
+        self.bias += nudge.bias_gradient;
+    }
+}
+
+/// LayerNorm has nothing to randomise, so this is the same as [LayerNorm::new]. It allows networks
+/// containing a LayerNorm to be randomised.
+#[cfg(feature = "random")]
+impl<T: RealField + Copy, const DIM: usize> RandomisableNetwork<T> for LayerNorm<T, DIM>
+where
+    Standard: Distribution<T>,
+{
+    fn random(_: &mut impl Rng) -> Self {
+        Self::new()
+    }
+}
+
+/// Tests
+mod test {
+    #[test]
+    fn layer_norm_test() {
+        use super::LayerNorm;
+        use crate::{activators::Linear, Network};
+        use nalgebra::Vector4;
+
+        let mut norm = LayerNorm::<f64, 2>::new();
+        norm.epsilon = 0.;
+
+        // 2 tokens, normalized independently
+        let outputs = norm.evaluate(Vector4::new(1., 3., 10., 0.), &Linear);
+        assert!((outputs - Vector4::new(-1., 1., 1., -1.)).norm() < 1e-9);
+
+        norm.gain.x = 2.;
+        norm.bias.y = 0.5;
+        let outputs = norm.evaluate(Vector4::new(1., 3., 10., 0.), &Linear);
+        assert!((outputs - Vector4::new(-2., 1.5, 2., -0.5)).norm() < 1e-9);
+    }
+}
@@ -1,8 +1,18 @@
 /// Defines the Exp struct which represents the Exp operation
 mod exp;
+/// Defines LayerNorm, which normalizes each token of a sequence
+mod layer_norm;
 /// Defines the Normalize struct which normalizes a vector passed into it
 mod normalize;
+/// Defines scalers, which standardise inputs using statistics fitted to a dataset
+mod scale;
 /// Defines the Softmax struct which performs Softmax
 mod softmax;
 
-pub use {exp::Exp, normalize::Normalize, softmax::Softmax};
+pub use {
+    exp::Exp,
+    layer_norm::LayerNorm,
+    normalize::Normalize,
+    scale::{MinMaxScaler, RobustScaler, StandardScaler},
+    softmax::Softmax,
+};
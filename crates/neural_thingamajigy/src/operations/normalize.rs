@@ -1,6 +1,6 @@
-use nalgebra::{RealField, SVector};
+use nalgebra::{RealField, SMatrix, SVector};
 
-use crate::Network;
+use crate::{LayerInfo, Network, NetworkInfo};
 
 /// Normalizes anything passed into it
 pub struct Normalize;
@@ -13,12 +13,30 @@ impl<T: RealField + Copy, const INPUTS: usize> Network<T, INPUTS, INPUTS> for No
     ) -> SVector<T, INPUTS> {
         inputs.normalize()
     }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        mut inputs: SMatrix<T, INPUTS, B>,
+        _: &impl crate::activators::Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        for mut column in inputs.column_iter_mut() {
+            column.normalize_mut();
+        }
+
+        inputs
+    }
 }
 
-#[cfg(feature = "train")]
-use {crate::TrainableNetwork, nalgebra::SMatrix};
+impl<const INPUTS: usize> NetworkInfo<INPUTS, INPUTS> for Normalize {
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        core::iter::once(LayerInfo::operation("normalize", "Normalize", INPUTS))
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::TrainableNetwork;
 
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
 impl<T: RealField + Copy, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUTS> for Normalize {
     type LayerInputs = SVector<T, INPUTS>;
 
@@ -64,9 +82,32 @@ impl<T: RealField + Copy, const INPUTS: usize> Network<T, INPUTS, INPUTS> for Ta
 
         inputs / sum
     }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        mut inputs: SMatrix<T, INPUTS, B>,
+        _: &impl crate::activators::Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        for mut column in inputs.column_iter_mut() {
+            let sum = column.sum();
+            column /= sum;
+        }
+
+        inputs
+    }
 }
 
-#[cfg(feature = "train")]
+impl<const INPUTS: usize> NetworkInfo<INPUTS, INPUTS> for TaxicabNormalize {
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        core::iter::once(LayerInfo::operation(
+            "taxicab_normalize",
+            "TaxicabNormalize",
+            INPUTS,
+        ))
+    }
+}
+
+#[cfg(feature = "backprop")]
 impl<T: RealField + Copy, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUTS>
     for TaxicabNormalize
 {
@@ -89,11 +130,12 @@ impl<T: RealField + Copy, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUT
         _: &impl crate::activators::Activator<T>,
     ) -> (Self::Gradient, SVector<T, INPUTS>) {
         let input_sum = layer_inputs.sum();
-        let dot = layer_inputs.dot(&output_loss_gradients);
+        let dot = layer_inputs.dot(&output_loss_gradients) / input_sum;
 
+        // every output depends on the sum, so each input shares the same dot product term
         (
             (),
-            output_loss_gradients / input_sum - layer_inputs * dot / (input_sum.powi(2)),
+            (output_loss_gradients - SVector::repeat(dot)) / input_sum,
         )
     }
 
@@ -127,4 +169,15 @@ mod test {
             &Sigmoid,
         );
     }
+
+    #[test]
+    fn test_taxicab_info() {
+        use crate::{operations::normalize::TaxicabNormalize, NetworkInfo};
+
+        let info = NetworkInfo::<3, 3>::layer_info(&TaxicabNormalize)
+            .next()
+            .unwrap();
+        assert_eq!((info.kind, info.outputs), ("TaxicabNormalize", 3));
+        assert_eq!(NetworkInfo::<3, 3>::parameter_count(&TaxicabNormalize), 0);
+    }
 }
@@ -0,0 +1,265 @@
+use nalgebra::{RealField, SMatrix, SVector};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{LayerInfo, Network, NetworkInfo};
+
+/// Standardises each input to have a mean of 0 and a standard deviation of 1
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StandardScaler<T: RealField, const INPUTS: usize> {
+    /// The mean of each input
+    pub mean: SVector<T, INPUTS>,
+    /// The standard deviation of each input
+    pub standard_deviation: SVector<T, INPUTS>,
+}
+
+impl<T: RealField + Copy, const INPUTS: usize> StandardScaler<T, INPUTS> {
+    /// Calculates the mean and standard deviation of every input in data
+    pub fn fit<'a>(data: impl IntoIterator<Item = &'a SVector<T, INPUTS>>) -> Self
+    where
+        T: 'a,
+    {
+        // Welford's algorithm, so data only needs to be iterated once
+        let mut count = T::zero();
+        let mut mean = SVector::<T, INPUTS>::zeros();
+        let mut squares = SVector::<T, INPUTS>::zeros();
+
+        for x in data {
+            count += T::one();
+            let delta = x - mean;
+            mean += delta / count;
+            squares += delta.component_mul(&(x - mean));
+        }
+
+        let variance = if count > T::zero() {
+            squares / count
+        } else {
+            squares
+        };
+
+        Self {
+            mean,
+            standard_deviation: nonzero(variance.map(|v| v.sqrt())),
+        }
+    }
+}
+
+/// Scales each input linearly so that the fitted data lies between 0 and 1
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinMaxScaler<T: RealField, const INPUTS: usize> {
+    /// The smallest value of each input
+    pub min: SVector<T, INPUTS>,
+    /// The difference between the largest and smallest value of each input
+    pub range: SVector<T, INPUTS>,
+}
+
+impl<T: RealField + Copy, const INPUTS: usize> MinMaxScaler<T, INPUTS> {
+    /// Finds the smallest and largest value of every input in data
+    pub fn fit<'a>(data: impl IntoIterator<Item = &'a SVector<T, INPUTS>>) -> Self
+    where
+        T: 'a,
+    {
+        let mut data = data.into_iter();
+        let Some(first) = data.next() else {
+            return Self {
+                min: SVector::zeros(),
+                range: SVector::repeat(T::one()),
+            };
+        };
+
+        let (min, max) = data.fold((*first, *first), |(min, max), x| (min.inf(x), max.sup(x)));
+
+        Self {
+            min,
+            range: nonzero(max - min),
+        }
+    }
+}
+
+/// Centres each input on its median and scales it by its interquartile range, making it
+/// less sensitive to outliers than [StandardScaler]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RobustScaler<T: RealField, const INPUTS: usize> {
+    /// The median of each input
+    pub median: SVector<T, INPUTS>,
+    /// The difference between the 75th and 25th percentile of each input
+    pub interquartile_range: SVector<T, INPUTS>,
+}
+
+#[cfg(feature = "std-train")]
+impl<T: RealField + Copy, const INPUTS: usize> RobustScaler<T, INPUTS> {
+    /// Calculates the median and interquartile range of every input in data
+    pub fn fit<'a>(data: impl IntoIterator<Item = &'a SVector<T, INPUTS>>) -> Self
+    where
+        T: 'a,
+    {
+        extern crate std;
+        use std::vec::Vec;
+
+        let data: Vec<_> = data.into_iter().collect();
+        let mut median = SVector::<T, INPUTS>::zeros();
+        let mut interquartile_range = SVector::<T, INPUTS>::zeros();
+
+        if !data.is_empty() {
+            for i in 0..INPUTS {
+                let mut column: Vec<T> = data.iter().map(|x| x[i]).collect();
+                column.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+
+                median[i] = quantile(&column, 2, 4);
+                interquartile_range[i] = quantile(&column, 3, 4) - quantile(&column, 1, 4);
+            }
+        }
+
+        Self {
+            median,
+            interquartile_range: nonzero(interquartile_range),
+        }
+    }
+}
+
+/// Returns the (numerator / denominator)th quantile of a sorted, non-empty slice, interpolating linearly
+#[cfg(feature = "std-train")]
+fn quantile<T: RealField + Copy>(sorted: &[T], numerator: usize, denominator: usize) -> T {
+    let position = (sorted.len() - 1) * numerator;
+    let (index, remainder) = (position / denominator, position % denominator);
+
+    if remainder == 0 {
+        sorted[index]
+    } else {
+        let fraction: T = nalgebra::convert(remainder as f64 / denominator as f64);
+        sorted[index] + (sorted[index + 1] - sorted[index]) * fraction
+    }
+}
+
+/// Replaces zero scales(from inputs that never change) with 1, so they are only shifted
+fn nonzero<T: RealField + Copy, const INPUTS: usize>(
+    scale: SVector<T, INPUTS>,
+) -> SVector<T, INPUTS> {
+    scale.map(|s| if s == T::zero() { T::one() } else { s })
+}
+
+/// Implements scaling, descaling, Network, NetworkInfo and TrainableNetwork for a scaler
+/// that computes `(x - offset) / scale`
+macro_rules! impl_scaler {
+    ($scaler:ident, $name:literal, $offset:ident, $scale:ident) => {
+        impl<T: RealField + Copy, const INPUTS: usize> $scaler<T, INPUTS> {
+            /// Scales inputs
+            pub fn transform(&self, inputs: SVector<T, INPUTS>) -> SVector<T, INPUTS> {
+                (inputs - self.$offset).component_div(&self.$scale)
+            }
+
+            /// Undoes the scaling, e.g. to turn a scaled regression output back into real units
+            pub fn inverse(&self, scaled: SVector<T, INPUTS>) -> SVector<T, INPUTS> {
+                scaled.component_mul(&self.$scale) + self.$offset
+            }
+        }
+
+        impl<T: RealField + Copy, const INPUTS: usize> Network<T, INPUTS, INPUTS>
+            for $scaler<T, INPUTS>
+        {
+            fn evaluate(
+                &self,
+                inputs: SVector<T, INPUTS>,
+                _: &impl crate::activators::Activator<T>,
+            ) -> SVector<T, INPUTS> {
+                self.transform(inputs)
+            }
+
+            fn evaluate_batch<const B: usize>(
+                &self,
+                mut inputs: SMatrix<T, INPUTS, B>,
+                _: &impl crate::activators::Activator<T>,
+            ) -> SMatrix<T, INPUTS, B> {
+                for mut column in inputs.column_iter_mut() {
+                    column -= self.$offset;
+                    column.component_div_assign(&self.$scale);
+                }
+
+                inputs
+            }
+        }
+
+        impl<T: RealField + Copy, const INPUTS: usize> NetworkInfo<INPUTS, INPUTS>
+            for $scaler<T, INPUTS>
+        {
+            fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+                // the fitted statistics aren't trained
+                core::iter::once(LayerInfo {
+                    parameters: 2 * INPUTS,
+                    ..LayerInfo::operation($name, stringify!($scaler), INPUTS)
+                })
+            }
+        }
+
+        #[cfg(feature = "backprop")]
+        impl<T: RealField + Copy, const INPUTS: usize> crate::TrainableNetwork<T, INPUTS, INPUTS>
+            for $scaler<T, INPUTS>
+        {
+            type LayerInputs = ();
+
+            // the fitted statistics are fixed, so there is nothing to train
+            type Gradient = ();
+
+            fn evaluate_training(
+                &self,
+                inputs: SVector<T, INPUTS>,
+                _: &impl crate::activators::Activator<T>,
+            ) -> (SVector<T, INPUTS>, Self::LayerInputs) {
+                (self.transform(inputs), ())
+            }
+
+            fn get_gradient(
+                &self,
+                _: &Self::LayerInputs,
+                output_loss_gradients: SVector<T, INPUTS>,
+                _: &impl crate::activators::Activator<T>,
+            ) -> (Self::Gradient, SVector<T, INPUTS>) {
+                ((), output_loss_gradients.component_div(&self.$scale))
+            }
+
+            fn apply_nudge(&mut self, _: Self::Gradient) {}
+        }
+    };
+}
+
+impl_scaler!(StandardScaler, "standard_scaler", mean, standard_deviation);
+impl_scaler!(MinMaxScaler, "min_max_scaler", min, range);
+impl_scaler!(RobustScaler, "robust_scaler", median, interquartile_range);
+
+/// Tests
+mod test {
+    #[test]
+    fn scaler_test() {
+        use super::{MinMaxScaler, RobustScaler, StandardScaler};
+        use crate::{activators::Linear, Network};
+        use nalgebra::Vector2;
+
+        let data = [
+            Vector2::new(1f64, 5.),
+            Vector2::new(2., 5.),
+            Vector2::new(3., 5.),
+            Vector2::new(10., 5.),
+        ];
+
+        let standard = StandardScaler::fit(&data);
+        assert!((standard.mean - Vector2::new(4., 5.)).norm() < 1e-9);
+        // the second input is constant, so it is only shifted
+        assert_eq!(standard.standard_deviation.y, 1.);
+        let scaled = standard.evaluate(data[3], &Linear);
+        assert!((scaled.x - 6. / 3.5355339).abs() < 1e-6);
+        assert!((standard.inverse(scaled) - data[3]).norm() < 1e-9);
+
+        let min_max = MinMaxScaler::fit(&data);
+        assert_eq!(min_max.transform(data[0]), Vector2::new(0., 0.));
+        assert_eq!(min_max.transform(data[3]), Vector2::new(1., 0.));
+
+        let robust = RobustScaler::fit(&data);
+        assert_eq!(robust.median, Vector2::new(2.5, 5.));
+        assert_eq!(robust.interquartile_range.x, 4.75 - 1.75);
+        assert!((robust.inverse(robust.transform(data[1])) - data[1]).norm() < 1e-9);
+    }
+}
@@ -1,6 +1,8 @@
-use nalgebra::{RealField, SVector};
+use nalgebra::{RealField, SMatrix, SVector};
 
-use crate::{operations::normalize::TaxicabNormalize, ChainableNetwork, Network};
+use crate::{
+    operations::normalize::TaxicabNormalize, ChainableNetwork, LayerInfo, Network, NetworkInfo,
+};
 
 use super::Exp;
 
@@ -13,14 +15,46 @@ impl<T: RealField + Copy, const INPUTS: usize> Network<T, INPUTS, INPUTS> for So
         inputs: SVector<T, INPUTS>,
         activator: &impl crate::activators::Activator<T>,
     ) -> SVector<T, INPUTS> {
-        Exp.chain(TaxicabNormalize).evaluate(inputs, activator)
+        Exp.chain(TaxicabNormalize)
+            .evaluate(shift_to_zero(inputs), activator)
+    }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        mut inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl crate::activators::Activator<T>,
+    ) -> SMatrix<T, INPUTS, B> {
+        for mut column in inputs.column_iter_mut() {
+            let shifted = shift_to_zero(column.clone_owned());
+            column.copy_from(&shifted);
+        }
+
+        Exp.chain(TaxicabNormalize)
+            .evaluate_batch(inputs, activator)
     }
 }
 
-#[cfg(feature = "train")]
-use crate::{operations::Normalize, TrainableNetwork};
+/// Shifts inputs so the largest is 0, so that Exp can't overflow. Softmax gives the same result
+/// for any shift, so this also doesn't change the gradient.
+fn shift_to_zero<T: RealField + Copy, const INPUTS: usize>(
+    inputs: SVector<T, INPUTS>,
+) -> SVector<T, INPUTS> {
+    let max = inputs
+        .iter()
+        .fold(T::min_value().unwrap_or(T::zero()), |a, &b| a.max(b));
+    inputs.add_scalar(-max)
+}
+
+impl<const INPUTS: usize> NetworkInfo<INPUTS, INPUTS> for Softmax {
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        core::iter::once(LayerInfo::operation("softmax", "Softmax", INPUTS))
+    }
+}
 
-#[cfg(feature = "train")]
+#[cfg(feature = "backprop")]
+use crate::TrainableNetwork;
+
+#[cfg(feature = "backprop")]
 impl<T: RealField + Copy, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUTS> for Softmax {
     type LayerInputs = (SVector<T, INPUTS>, SVector<T, INPUTS>);
 
@@ -31,7 +65,8 @@ impl<T: RealField + Copy, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUT
         inputs: nalgebra::SVector<T, INPUTS>,
         activator: &impl crate::activators::Activator<T>,
     ) -> (nalgebra::SVector<T, INPUTS>, Self::LayerInputs) {
-        Exp.chain(Normalize).evaluate_training(inputs, activator)
+        Exp.chain(TaxicabNormalize)
+            .evaluate_training(shift_to_zero(inputs), activator)
     }
 
     fn get_gradient(
@@ -42,7 +77,7 @@ impl<T: RealField + Copy, const INPUTS: usize> TrainableNetwork<T, INPUTS, INPUT
     ) -> (Self::Gradient, nalgebra::SVector<T, INPUTS>) {
         (
             (),
-            Exp.chain(Normalize)
+            Exp.chain(TaxicabNormalize)
                 .get_gradient(layer_inputs, output_loss_gradients, activator)
                 .1,
         )
@@ -62,5 +97,26 @@ mod test {
         let softmaxed = Softmax.evaluate(Vector3::new(1f32, 2f32, 3f32), &Linear);
         assert!((softmaxed.norm() - 1f32) < 0.001f32);
         assert!((softmaxed - Vector3::new(0.090031f32, 0.244728f32, 0.665241f32)).norm() < 0.0001);
+
+        // large inputs shouldn't overflow
+        let softmaxed = Softmax.evaluate(Vector3::new(1000f32, 1000f32, 0f32), &Linear);
+        assert!((softmaxed - Vector3::new(0.5f32, 0.5f32, 0f32)).norm() < 0.0001);
+    }
+
+    #[cfg(feature = "backprop")]
+    #[test]
+    fn softmax_gradient_test() {
+        use crate::{activators::Linear, operations::softmax::Softmax, TrainableNetwork};
+        use nalgebra::Vector3;
+
+        let inputs = Vector3::new(0.5f64, -1., 2.);
+        let loss_gradients = Vector3::new(1., 2., -3.);
+        let (outputs, layer_inputs) = Softmax.evaluate_training(inputs, &Linear);
+        let (_, gradient) = Softmax.get_gradient(&layer_inputs, loss_gradients, &Linear);
+
+        // the jacobian of softmax is diag(s) - s s^T
+        let expected =
+            outputs.component_mul(&loss_gradients) - outputs * outputs.dot(&loss_gradients);
+        assert!((gradient - expected).norm() < 1e-12);
     }
 }
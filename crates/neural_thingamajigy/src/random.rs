@@ -0,0 +1,16 @@
+use nalgebra::{RealField, SVector};
+use rand::Rng;
+
+/// Draws a vector from a standard normal distribution, using the Box-Muller transform
+#[cfg_attr(not(feature = "std-train"), allow(dead_code))] // only the training helpers use this for now
+pub(crate) fn standard_normal<T: RealField + Copy, const N: usize>(
+    rng: &mut impl Rng,
+) -> SVector<T, N> {
+    SVector::from_fn(|_, _| {
+        // 1 - u is in (0, 1], so its logarithm is finite
+        let u: T = nalgebra::convert(1. - rng.gen::<f64>());
+        let v: T = nalgebra::convert(rng.gen::<f64>());
+
+        (-(u.ln() + u.ln())).sqrt() * (T::two_pi() * v).cos()
+    })
+}
@@ -0,0 +1,145 @@
+use nalgebra::{RealField, SVector};
+
+use crate::{activators::Activator, LayerInfo, Network, NetworkInfo};
+
+/// A [crate::Layer] whose weights are stored in compressed sparse row form, so pruned(zero)
+/// weights take no space and are skipped during evaluation. It borrows its weights, so they can
+/// be `static` arrays in flash, and evaluating it doesn't allocate.
+///
+/// The non-zero weights of output `i` are `values[row_starts[i]..row_starts[i + 1]]`, and each
+/// multiplies the input at the same position in columns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SparseLayer<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> {
+    /// Where each output's weights start in columns and values, followed by the number of values
+    row_starts: &'a [u32],
+    /// The input each value multiplies
+    columns: &'a [u16],
+    /// The non-zero weights
+    values: &'a [T],
+    /// The bias of each output
+    bias: &'a [T],
+}
+
+impl<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>
+    SparseLayer<'a, T, INPUTS, OUTPUTS>
+{
+    /// Creates a sparse layer from its compressed rows, see [SparseLayer]
+    /// # Panics
+    /// If there aren't `OUTPUTS + 1` row_starts, which don't increase from 0 to the number of
+    /// values, a column for each value and a column isn't less than `INPUTS`, or there aren't
+    /// `OUTPUTS` biases
+    pub fn new(row_starts: &'a [u32], columns: &'a [u16], values: &'a [T], bias: &'a [T]) -> Self {
+        assert_eq!(row_starts.len(), OUTPUTS + 1, "need OUTPUTS + 1 row starts");
+        assert!(
+            row_starts.first() == Some(&0)
+                && row_starts.windows(2).all(|w| w[0] <= w[1])
+                && row_starts[OUTPUTS] as usize == values.len(),
+            "row starts must increase from 0 to the number of values"
+        );
+        assert_eq!(columns.len(), values.len(), "need a column for each value");
+        assert!(
+            columns.iter().all(|&c| (c as usize) < INPUTS),
+            "columns must be less than INPUTS"
+        );
+        assert_eq!(bias.len(), OUTPUTS, "need OUTPUTS biases");
+
+        Self {
+            row_starts,
+            columns,
+            values,
+            bias,
+        }
+    }
+
+    /// The number of non-zero weights
+    pub fn non_zero(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> Network<T, INPUTS, OUTPUTS>
+    for SparseLayer<'_, T, INPUTS, OUTPUTS>
+{
+    fn evaluate(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, OUTPUTS> {
+        SVector::from_fn(|i, _| {
+            let row = self.row_starts[i] as usize..self.row_starts[i + 1] as usize;
+            let weighted = self.columns[row.clone()]
+                .iter()
+                .zip(&self.values[row])
+                .fold(T::zero(), |total, (&column, &value)| {
+                    total + value * inputs[column as usize]
+                });
+
+            activator.activation(weighted) + self.bias[i]
+        })
+    }
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> NetworkInfo<INPUTS, OUTPUTS>
+    for SparseLayer<'_, T, INPUTS, OUTPUTS>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        // only the non-zero weights are stored, and the borrowed weights can't be trained
+        core::iter::once(LayerInfo {
+            name: "sparse_layer",
+            kind: "SparseLayer",
+            inputs: INPUTS,
+            outputs: OUTPUTS,
+            parameters: self.non_zero() + OUTPUTS,
+            trainable_parameters: 0,
+        })
+    }
+}
+
+#[cfg(feature = "std-train")]
+extern crate std;
+#[cfg(feature = "std-train")]
+use std::vec::Vec;
+
+/// An owned [SparseLayer], created by [crate::Layer::to_sparse]. Its fields can be written out
+/// as `static` arrays and loaded with [SparseLayer::new].
+#[cfg(feature = "std-train")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseLayerBuf<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize> {
+    /// Where each output's weights start in columns and values, followed by the number of values
+    pub row_starts: Vec<u32>,
+    /// The input each value multiplies
+    pub columns: Vec<u16>,
+    /// The non-zero weights
+    pub values: Vec<T>,
+    /// The bias of each output
+    pub bias: Vec<T>,
+}
+
+#[cfg(feature = "std-train")]
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>
+    SparseLayerBuf<T, INPUTS, OUTPUTS>
+{
+    /// Borrows this as a [SparseLayer]
+    pub fn as_layer(&self) -> SparseLayer<'_, T, INPUTS, OUTPUTS> {
+        SparseLayer::new(&self.row_starts, &self.columns, &self.values, &self.bias)
+    }
+}
+
+/// Tests
+mod test {
+    #[test]
+    fn sparse_test() {
+        use super::SparseLayer;
+        use crate::{activators::Linear, Network};
+        use nalgebra::{Vector2, Vector3};
+
+        // [[1, 0, 2], [0, 0, -1]]
+        let layer =
+            SparseLayer::<f64, 3, 2>::new(&[0, 2, 3], &[0, 2, 2], &[1., 2., -1.], &[0.5, 0.]);
+        assert_eq!(layer.non_zero(), 3);
+        assert_eq!(
+            layer.evaluate(Vector3::new(1., 2., 3.), &Linear),
+            Vector2::new(7.5, -3.)
+        );
+    }
+}
@@ -2,20 +2,34 @@ extern crate std;
 
 use crate::{
     activators::Activator,
-    network::{Network, TrainableNetwork},
-    valueset::mean,
+    backprop::{loss, non_finite_part, optimiser, sample_gradient, TrainError},
+    network::{BatchTrainableNetwork, Network, TrainableNetwork},
+    valueset::{Accumulator, ValueSet},
 };
-use loss::LossFunction;
-use nalgebra::{RealField, SVector};
+use loss::{Loss, ReducedLoss, Reduction};
+use nalgebra::{RealField, SMatrix, SVector};
 use optimiser::Optimiser;
-use std::{iter::Sum, vec::Vec};
+use std::iter::Sum;
+use std::vec::Vec;
 
-/// Defines LossFunction and some common instances
-pub mod loss;
-/// Defines Optimiser trait and ADAM
-pub mod optimiser;
+/// Defines FGSM and PGD adversarial examples, adversarial training and robustness reports
+pub mod adversarial;
+/// Defines Checkpoint, which holds everything needed to resume training
+#[cfg(feature = "serde")]
+pub mod checkpoint;
+/// Defines Distiller, which trains a small student network to imitate a larger teacher
+pub mod distill;
+/// Defines saliency methods for explaining which inputs drove a prediction
+pub mod explain;
+/// Defines classification and regression metrics for evaluating networks
+pub mod metrics;
+/// Defines magnitude pruning, and Pruned which keeps pruned weights at zero while training
+pub mod pruning;
 
-pub use crate::layer::LayerGradient;
+/// How many consecutive samples have their gradients summed together before being combined
+/// with the rest. [accumulate] and `train_parallel` sum in chunks of this size, in order, so
+/// they produce identical results however many threads are used.
+pub const GRADIENT_CHUNK_SIZE: usize = 64;
 
 /// Perform 1 training epoch on a network with training data.
 /// `data` is a slice of `(INPUT, OUTPUT)` tuples. This returns
@@ -23,7 +37,7 @@ pub use crate::layer::LayerGradient;
 /// loss_function.
 pub fn train<
     'a,
-    T: RealField + Copy + From<u8>,
+    T: RealField + Copy,
     N: TrainableNetwork<T, INPUTS, OUTPUTS>,
     const INPUTS: usize,
     const OUTPUTS: usize,
@@ -31,34 +45,362 @@ pub fn train<
     data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
     network: &mut N,
     activator: &impl Activator<T>,
-    loss_function: &LossFunction<T, OUTPUTS>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
     optimiser: &mut impl Optimiser<T, N::Gradient>,
-) -> T {
+) -> Result<T, TrainError> {
+    let mut accumulator = Accumulator::new();
+
+    let total_loss = accumulate(data, network, activator, loss_function, &mut accumulator)?;
+    let count = accumulator.count();
+
+    apply_accumulated(network, optimiser, &mut accumulator)?;
+
+    Ok(total_loss / nalgebra::convert(count as f64))
+}
+
+/// Like [train], but evaluates and backpropogates `B` samples at a time as the columns of a
+/// matrix, using matrix-matrix multiplication. This is much faster for wide layers, and gives
+/// the same result up to rounding. `B` must be at least 1, which is checked at compile time.
+pub fn train_batched<
+    const B: usize,
+    T: RealField + Copy,
+    N: BatchTrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: &[(SVector<T, INPUTS>, SVector<T, OUTPUTS>)],
+    network: &mut N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+) -> Result<T, TrainError> {
+    const { assert!(B > 0, "batches must hold at least 1 sample") };
+
+    let mut accumulator = Accumulator::new();
     let mut total_loss = T::zero();
 
-    let gradients: Vec<N::Gradient> = data
-        .map(
-            #[expect(non_snake_case)]
-            |(x, Y)| {
-                let (predicted, training_data) = network.evaluate_training(*x, activator);
+    for (batch, chunk) in data.chunks(B).enumerate() {
+        // the last batch is padded with its first sample, which has no loss gradient
+        let inputs =
+            SMatrix::<T, INPUTS, B>::from_fn(|i, j| chunk.get(j).unwrap_or(&chunk[0]).0[i]);
+        let (predicted, layer_inputs) = network.evaluate_training_batch(inputs, activator);
 
-                let (instance_loss, loss_gradient) = loss_function(Y, &predicted);
-                total_loss += instance_loss;
+        let mut loss_gradients = SMatrix::<T, OUTPUTS, B>::zeros();
+        for (j, (_, expected)) in chunk.iter().enumerate() {
+            let (instance_loss, loss_gradient) =
+                loss_function.loss(expected, &predicted.column(j).into_owned());
+            if !instance_loss.is_finite() {
+                return Err(TrainError::NonFiniteLoss {
+                    sample: batch * B + j,
+                });
+            }
 
-                network
-                    .get_gradient(&training_data, loss_gradient, activator)
-                    .0 // discard network input loss as this isn't deep learning
-            },
-        )
-        .collect();
+            total_loss += instance_loss;
+            loss_gradients.set_column(j, &loss_gradient);
+        }
 
-    let gradient = mean(gradients.as_slice()); // mean error
+        let (gradient, _) = network.get_gradient_batch(&layer_inputs, loss_gradients, activator);
+        accumulator.add_sum(&gradient, chunk.len());
+    }
+    let count = accumulator.count();
+
+    apply_accumulated(network, optimiser, &mut accumulator)?;
+
+    Ok(total_loss / nalgebra::convert(count as f64))
+}
+
+/// Adds the gradient of every sample in data to accumulator, without changing the network,
+/// returning the total loss. Calling this several times before [apply_accumulated] trains with
+/// a larger effective batch, in memory that doesn't grow with the number of samples.
+/// ## Example
+/// ```rust
+///     use nalgebra::{Vector1, Vector2};
+///     use neural_thingamajigy::{
+///         accumulate, activators::Relu, apply_accumulated, loss::squared_error, network,
+///         optimiser::AdamOptimiser, valueset::Accumulator, RandomisableNetwork,
+///     };
+///
+///     network!(pub MyNetwork, f32, 2, 3, 1);
+///     let mut network = MyNetwork::random(&mut rand::rngs::OsRng);
+///     let mut opt = AdamOptimiser::default();
+///
+///     // Compensated summation keeps f32 sums accurate over many batches
+///     let mut accumulator = Accumulator::compensated();
+///     for batch in 0..4 {
+///         let data = [(Vector2::new(batch as f32, 1f32), Vector1::new(1f32))];
+///         accumulate(data.iter(), &network, &Relu::default(), &squared_error, &mut accumulator)
+///             .unwrap();
+///     }
+///     // One step using the mean gradient of all 4 batches
+///     apply_accumulated(&mut network, &mut opt, &mut accumulator).unwrap();
+/// ```
+pub fn accumulate<
+    'a,
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    accumulator: &mut Accumulator<T, N::Gradient>,
+) -> Result<T, TrainError> {
+    accumulate_samples(
+        data.map(|(x, y)| (x, y, T::one())),
+        network,
+        activator,
+        loss_function,
+        accumulator,
+    )
+}
+
+/// Like [accumulate], but for `(input, expected output, weight)` samples, so that the expected
+/// outputs don't need to be stored alongside the inputs(e.g. when they're the same).
+pub(crate) fn accumulate_samples<
+    'a,
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: impl Iterator<Item = (&'a SVector<T, INPUTS>, &'a SVector<T, OUTPUTS>, T)>,
+    network: &N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    accumulator: &mut Accumulator<T, N::Gradient>,
+) -> Result<T, TrainError> {
+    let mut data = data.peekable();
+    let mut total_loss = T::zero();
+    let mut first_sample = 0;
+
+    while data.peek().is_some() {
+        let (chunk_loss, chunk) = accumulate_chunk(
+            data.by_ref().take(GRADIENT_CHUNK_SIZE),
+            first_sample,
+            network,
+            activator,
+            loss_function,
+            accumulator.empty_like(),
+            None,
+        )?;
+
+        total_loss += chunk_loss;
+        first_sample += chunk.count();
+        accumulator.merge(&chunk);
+    }
+
+    Ok(total_loss)
+}
+
+/// Nudges the network by the optimised mean of the gradients in accumulator, then empties it.
+/// If the accumulator is empty or the gradient isn't finite, the network is left unchanged.
+pub fn apply_accumulated<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &mut N,
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+    accumulator: &mut Accumulator<T, N::Gradient>,
+) -> Result<(), TrainError> {
+    let count: T = nalgebra::convert(accumulator.count() as f64);
+    apply_scaled(network, optimiser, accumulator, T::one() / count) // mean error
+}
+
+/// Perform 1 training epoch, multiplying the loss of `data[i]` by `sample_weights[i]`, and
+/// combining the losses of every sample using reduction. [Reduction::Mean] divides the weighted
+/// losses and gradients by the sum of the weights rather than the number of samples, so scaling
+/// every weight doesn't change the step. Returns [TrainError::ShapeMismatch] if there isn't
+/// exactly 1 weight per sample, and [TrainError::EmptyDataset] if there are no samples or(when
+/// taking the mean) the weights sum to 0.
+pub fn train_weighted<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: &[(SVector<T, INPUTS>, SVector<T, OUTPUTS>)],
+    sample_weights: &[T],
+    network: &mut N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    reduction: Reduction,
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+) -> Result<ReducedLoss<T>, TrainError> {
+    if sample_weights.len() != data.len() {
+        return Err(TrainError::ShapeMismatch {
+            expected: data.len(),
+            found: sample_weights.len(),
+        });
+    }
+
+    let total_weight = sample_weights.iter().fold(T::zero(), |total, &w| total + w);
+    let scale = match reduction {
+        Reduction::Mean if total_weight.is_zero() => return Err(TrainError::EmptyDataset),
+        Reduction::Mean => T::one() / total_weight,
+        Reduction::Sum | Reduction::None => T::one(),
+    };
+
+    let mut losses = Vec::with_capacity(data.len());
+    let (_, mut accumulator) = accumulate_chunk(
+        data.iter()
+            .zip(sample_weights)
+            .map(|((x, y), weight)| (x, y, *weight)),
+        0,
+        network,
+        activator,
+        loss_function,
+        Accumulator::new(),
+        Some(&mut losses),
+    )?;
+
+    apply_scaled(network, optimiser, &mut accumulator, scale)?;
+
+    Ok(match reduction {
+        Reduction::Mean => {
+            ReducedLoss::Scalar(losses.into_iter().fold(T::zero(), |a, b| a + b) * scale)
+        }
+        _ => reduction.reduce(losses)?,
+    })
+}
+
+/// Nudges the network by the optimised sum of the gradients in accumulator multiplied by scale,
+/// then empties it
+fn apply_scaled<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &mut N,
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+    accumulator: &mut Accumulator<T, N::Gradient>,
+    scale: T,
+) -> Result<(), TrainError> {
+    if accumulator.count() == 0 {
+        return Err(TrainError::EmptyDataset);
+    }
+
+    let gradient = accumulator.sum().unary_operation(|&g| g * scale);
+    accumulator.reset();
+
+    if let Some(layer) = non_finite_part(&gradient) {
+        return Err(TrainError::NonFiniteGradient { layer: Some(layer) });
+    }
 
     let step = optimiser.transform(&gradient);
 
     network.apply_nudge(step);
 
-    total_loss / (gradients.len() as u8).into()
+    Ok(())
+}
+
+/// Like [train], but splits data between every available thread to calculate gradients.
+/// This produces exactly the same result as [train].
+#[cfg(feature = "parallel")]
+pub fn train_parallel<
+    T: RealField + Copy + Send,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS> + Sync,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: &[(SVector<T, INPUTS>, SVector<T, OUTPUTS>)],
+    network: &mut N,
+    activator: &(impl Activator<T> + Sync),
+    loss_function: &(impl Loss<T, OUTPUTS> + Sync + ?Sized),
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+) -> Result<T, TrainError>
+where
+    N::Gradient: Send,
+{
+    let chunks: Vec<_> = data.chunks(GRADIENT_CHUNK_SIZE).enumerate().collect();
+    let threads = std::thread::available_parallelism().map_or(1, |t| t.get());
+    let chunks_per_thread = chunks.len().div_ceil(threads).max(1);
+
+    let shared_network: &N = network;
+    let partial_sums: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .chunks(chunks_per_thread)
+            .map(|thread_chunks| {
+                scope.spawn(move || {
+                    thread_chunks
+                        .iter()
+                        .map(|(i, chunk)| {
+                            accumulate_chunk(
+                                chunk.iter().map(|(x, y)| (x, y, T::one())),
+                                i * GRADIENT_CHUNK_SIZE,
+                                shared_network,
+                                activator,
+                                loss_function,
+                                Accumulator::new(),
+                                None,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        // joining in spawn order keeps the chunks in order
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect()
+    });
+
+    let mut accumulator = Accumulator::new();
+    let mut total_loss = T::zero();
+    for partial_sum in partial_sums {
+        let (chunk_loss, chunk) = partial_sum?;
+        total_loss += chunk_loss;
+        accumulator.merge(&chunk);
+    }
+    let count = accumulator.count();
+
+    apply_accumulated(network, optimiser, &mut accumulator)?;
+
+    Ok(total_loss / nalgebra::convert(count as f64))
+}
+
+/// Adds the gradients of a chunk of `(input, expected output, weight)` samples to accumulator, returning the chunk's
+/// total loss and the accumulator. first_sample is the index of the chunk's first sample, for
+/// reporting errors. The weighted loss of each sample is pushed to sample_losses, if given.
+fn accumulate_chunk<
+    'a,
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: impl Iterator<Item = (&'a SVector<T, INPUTS>, &'a SVector<T, OUTPUTS>, T)>,
+    first_sample: usize,
+    network: &N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    mut accumulator: Accumulator<T, N::Gradient>,
+    mut sample_losses: Option<&mut Vec<T>>,
+) -> Result<(T, Accumulator<T, N::Gradient>), TrainError> {
+    let mut total_loss = T::zero();
+
+    for (sample, (x, y, weight)) in (first_sample..).zip(data) {
+        let (instance_loss, gradient) =
+            sample_gradient(*x, y, weight, sample, network, activator, loss_function)?;
+
+        total_loss += instance_loss;
+        if let Some(losses) = sample_losses.as_mut() {
+            losses.push(instance_loss);
+        }
+        accumulator.add(&gradient);
+    }
+
+    Ok((total_loss, accumulator))
 }
 
 /// Calculates the average loss for a network from a set of data
@@ -66,15 +408,21 @@ pub fn get_loss<'a, T: RealField + Copy + Sum, const INPUTS: usize, const OUTPUT
     data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
     network: &impl Network<T, INPUTS, OUTPUTS>,
     activator: &impl Activator<T>,
-    loss_function: &LossFunction<T, OUTPUTS>,
-) -> T {
-    let mut counter = T::zero();
-    data.inspect(|_| counter += T::one())
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+) -> Result<T, TrainError> {
+    let mut counter = 0usize;
+    let total = data
+        .inspect(|_| counter += 1)
         .map(|(input, expected)| {
             let predicted = network.evaluate(*input, activator);
 
-            loss_function(expected, &predicted).0
+            loss_function.loss(expected, &predicted).0
         })
-        .sum::<T>()
-        / counter
+        .sum::<T>();
+
+    if counter == 0 {
+        return Err(TrainError::EmptyDataset);
+    }
+
+    Ok(total / nalgebra::convert(counter as f64))
 }
@@ -0,0 +1,343 @@
+extern crate std;
+
+use super::{
+    accumulate_samples, apply_accumulated,
+    explain::input_gradient,
+    get_loss,
+    loss::{sign, Loss},
+    metrics::accuracy,
+    optimiser::Optimiser,
+    TrainError,
+};
+use crate::{
+    activators::Activator,
+    network::{Network, TrainableNetwork},
+    valueset::Accumulator,
+};
+use nalgebra::{RealField, SVector};
+use std::{iter::Sum, vec::Vec};
+
+/// How far an adversarial example may be from the original inputs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Budget<T> {
+    /// No input may change by more than this
+    LInfinity(T),
+    /// The euclidean length of the change may not be more than this
+    L2(T),
+}
+
+impl<T: RealField + Copy> Budget<T> {
+    /// The largest step of length size(measured by this budget's norm) in the direction of
+    /// gradient, which is how FGSM and each PGD iteration perturb their inputs
+    pub fn step<const N: usize>(&self, gradient: SVector<T, N>, size: T) -> SVector<T, N> {
+        match self {
+            Self::LInfinity(_) => gradient.map(|g| sign(g) * size),
+            Self::L2(_) => {
+                let norm = gradient.norm();
+                if norm > T::zero() {
+                    gradient * (size / norm)
+                } else {
+                    gradient
+                }
+            }
+        }
+    }
+
+    /// Moves a perturbation back inside the budget
+    pub fn project<const N: usize>(&self, perturbation: SVector<T, N>) -> SVector<T, N> {
+        match *self {
+            Self::LInfinity(epsilon) => perturbation.map(|p| p.clamp(-epsilon, epsilon)),
+            Self::L2(epsilon) => {
+                let norm = perturbation.norm();
+                if norm > epsilon {
+                    perturbation * (epsilon / norm)
+                } else {
+                    perturbation
+                }
+            }
+        }
+    }
+
+    /// The size of the budget
+    pub fn epsilon(&self) -> T {
+        match *self {
+            Self::LInfinity(epsilon) | Self::L2(epsilon) => epsilon,
+        }
+    }
+}
+
+/// How adversarial examples are generated
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Attack<T> {
+    /// The fast gradient sign method, a single step of the whole budget up the loss gradient
+    Fgsm(Budget<T>),
+    /// Projected gradient descent(on the negated loss), steps of step_size up the loss gradient,
+    /// each projected back inside budget. Slower, but finds stronger examples than FGSM.
+    Pgd {
+        /// How far the inputs may be moved
+        budget: Budget<T>,
+        /// How far each step moves the inputs
+        step_size: T,
+        /// The number of steps
+        steps: usize,
+    },
+}
+
+impl<T: RealField + Copy> Attack<T> {
+    /// Returns inputs perturbed within the budget to increase the network's loss on expected
+    pub fn perturb<
+        N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+        const INPUTS: usize,
+        const OUTPUTS: usize,
+    >(
+        &self,
+        network: &N,
+        inputs: SVector<T, INPUTS>,
+        expected: &SVector<T, OUTPUTS>,
+        activator: &impl Activator<T>,
+        loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    ) -> SVector<T, INPUTS> {
+        match *self {
+            Self::Fgsm(budget) => fgsm(network, inputs, expected, activator, loss_function, budget),
+            Self::Pgd {
+                budget,
+                step_size,
+                steps,
+            } => pgd(
+                network,
+                inputs,
+                expected,
+                activator,
+                loss_function,
+                budget,
+                step_size,
+                steps,
+            ),
+        }
+    }
+}
+
+/// The gradient of the loss with respect to the inputs
+fn loss_input_gradient<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    expected: &SVector<T, OUTPUTS>,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+) -> SVector<T, INPUTS> {
+    let predicted = network.evaluate_training(inputs, activator).0;
+    let (_, loss_gradient) = loss_function.loss(expected, &predicted);
+
+    input_gradient(network, inputs, activator, loss_gradient)
+}
+
+/// The fast gradient sign method, returning inputs moved by the whole budget in the direction
+/// that increases the loss fastest
+pub fn fgsm<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    expected: &SVector<T, OUTPUTS>,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    budget: Budget<T>,
+) -> SVector<T, INPUTS> {
+    let gradient = loss_input_gradient(network, inputs, expected, activator, loss_function);
+
+    inputs + budget.step(gradient, budget.epsilon())
+}
+
+/// Projected gradient descent, returning inputs moved steps times by step_size in the direction
+/// that increases the loss fastest, staying within budget of the original inputs
+#[expect(clippy::too_many_arguments)]
+pub fn pgd<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    expected: &SVector<T, OUTPUTS>,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    budget: Budget<T>,
+    step_size: T,
+    steps: usize,
+) -> SVector<T, INPUTS> {
+    let mut perturbation = SVector::<T, INPUTS>::zeros();
+
+    for _ in 0..steps {
+        let gradient = loss_input_gradient(
+            network,
+            inputs + perturbation,
+            expected,
+            activator,
+            loss_function,
+        );
+        perturbation = budget.project(perturbation + budget.step(gradient, step_size));
+    }
+
+    inputs + perturbation
+}
+
+/// Perform 1 training epoch on a mix of data and adversarial examples generated from it by
+/// attack against the network as it was at the start of the epoch. Each sample's loss is
+/// `1 - adversarial_weight` times its clean loss plus `adversarial_weight` times its
+/// adversarial loss, and the average is returned.
+pub fn train_adversarial<
+    'a,
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &mut N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    optimiser: &mut impl Optimiser<T, N::Gradient>,
+    attack: &Attack<T>,
+    adversarial_weight: T,
+) -> Result<T, TrainError> {
+    let data: Vec<_> = data.collect();
+    let adversarial: Vec<_> = data
+        .iter()
+        .map(|(x, y)| attack.perturb(network, *x, y, activator, loss_function))
+        .collect();
+
+    // every sample is counted twice, so the weights are doubled to keep the mean the same
+    let two: T = nalgebra::convert(2.);
+    let clean_weight = (T::one() - adversarial_weight) * two;
+    let adversarial_weight = adversarial_weight * two;
+
+    let samples = data
+        .iter()
+        .zip(&adversarial)
+        .flat_map(|((x, y), adversarial)| {
+            [(x, y, clean_weight), (adversarial, y, adversarial_weight)]
+        });
+
+    let mut accumulator = Accumulator::new();
+    let total_loss =
+        accumulate_samples(samples, network, activator, loss_function, &mut accumulator)?;
+    let count = accumulator.count();
+
+    apply_accumulated(network, optimiser, &mut accumulator)?;
+
+    Ok(total_loss / nalgebra::convert(count as f64))
+}
+
+/// How well a network holds up against adversarial examples, see [robustness]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RobustnessReport<T> {
+    /// The average loss on the original data
+    pub clean_loss: T,
+    /// The average loss on the adversarial examples
+    pub adversarial_loss: T,
+    /// The accuracy on the original data, see [crate::metrics::accuracy]
+    pub clean_accuracy: T,
+    /// The accuracy on the adversarial examples
+    pub adversarial_accuracy: T,
+    /// The largest loss increase of any sample
+    pub worst_loss_increase: T,
+}
+
+/// Attacks every sample in data, comparing the network's loss and accuracy before and after.
+/// The accuracy is only meaningful for classifiers.
+pub fn robustness<
+    'a,
+    T: RealField + Copy + Sum,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS> + Network<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &N,
+    activator: &impl Activator<T>,
+    loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+    attack: &Attack<T>,
+) -> Result<RobustnessReport<T>, TrainError> {
+    let data: Vec<_> = data.collect();
+    let adversarial: Vec<_> = data
+        .iter()
+        .map(|(x, y)| (attack.perturb(network, *x, y, activator, loss_function), *y))
+        .collect();
+
+    let worst_loss_increase = data
+        .iter()
+        .zip(&adversarial)
+        .map(|((x, y), (adversarial, _))| {
+            loss_function
+                .loss(y, &network.evaluate(*adversarial, activator))
+                .0
+                - loss_function.loss(y, &network.evaluate(*x, activator)).0
+        })
+        .fold(T::zero(), |worst, increase| worst.max(increase));
+
+    Ok(RobustnessReport {
+        clean_loss: get_loss(data.iter().copied(), network, activator, loss_function)?,
+        adversarial_loss: get_loss(adversarial.iter(), network, activator, loss_function)?,
+        clean_accuracy: accuracy(data.iter().copied(), network, activator)?,
+        adversarial_accuracy: accuracy(adversarial.iter(), network, activator)?,
+        worst_loss_increase,
+    })
+}
+
+/// Tests
+mod test {
+    #[test]
+    fn adversarial_test() {
+        use super::{fgsm, pgd, robustness, Attack, Budget};
+        use crate::{activators::Linear, loss::squared_error, operations::Exp};
+        use nalgebra::Vector2;
+
+        // both outputs are too large, so increasing either input increases the loss
+        let inputs = Vector2::new(0f64, 0.);
+        let expected = Vector2::new(0., 0.);
+
+        let linf = Budget::LInfinity(0.1);
+        let attacked = fgsm(&Exp, inputs, &expected, &Linear, &squared_error, linf);
+        assert_eq!(attacked, Vector2::new(0.1, 0.1));
+
+        // PGD should end up on the edge of the budget, in the direction of the gradient
+        let l2 = Budget::L2(0.1);
+        let attacked = pgd(
+            &Exp,
+            inputs,
+            &expected,
+            &Linear,
+            &squared_error,
+            l2,
+            0.03,
+            10,
+        );
+        assert!(((attacked - inputs).norm() - 0.1).abs() < 1e-12);
+        assert!((attacked[0] - attacked[1]).abs() < 1e-12);
+
+        let data = [(inputs, expected)];
+        let report = robustness(
+            data.iter(),
+            &Exp,
+            &Linear,
+            &squared_error,
+            &Attack::Fgsm(linf),
+        )
+        .unwrap();
+        assert_eq!(report.clean_loss, 2.);
+        assert!((report.adversarial_loss - 2. * 0.2f64.exp()).abs() < 1e-12);
+        assert_eq!(
+            report.worst_loss_increase,
+            report.adversarial_loss - report.clean_loss
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Everything needed to resume an interrupted training run exactly where it stopped.
+/// Serializing the optimiser with the model preserves its momentum and bias correction,
+/// which would otherwise restart from scratch. `R` should be a serializable, seedable RNG,
+/// such as `rand_chacha::ChaCha8Rng` with its `serde1` feature.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint<N, O, R> {
+    /// The network being trained
+    pub network: N,
+    /// The optimiser, including its accumulated state
+    pub optimiser: O,
+    /// How many epochs have been completed
+    pub epoch: usize,
+    /// The random number generator used during training, e.g. for shuffling
+    pub rng: R,
+}
+
+impl<N, O, R> Checkpoint<N, O, R> {
+    /// Creates a new checkpoint
+    pub fn new(network: N, optimiser: O, epoch: usize, rng: R) -> Self {
+        Self {
+            network,
+            optimiser,
+            epoch,
+            rng,
+        }
+    }
+}
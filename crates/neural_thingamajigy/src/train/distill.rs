@@ -0,0 +1,151 @@
+use super::{apply_accumulated, loss::Loss, optimiser::Optimiser, TrainError};
+use crate::{
+    activators::{Activator, Linear},
+    network::{Network, TrainableNetwork},
+    operations::Softmax,
+    valueset::Accumulator,
+};
+use nalgebra::{RealField, SVector};
+
+/// Distills a (usually large) teacher network into a smaller student with the same inputs and
+/// outputs. The student learns from the teacher's soft targets, the softmax of its outputs
+/// divided by temperature, as well as from the expected outputs(hard labels).
+///
+/// The outputs of both networks are treated as logits, so classifiers shouldn't end in a softmax.
+pub struct Distiller<T, N, A> {
+    /// The network being distilled
+    pub teacher: N,
+    /// The activator used to evaluate the teacher
+    pub activator: A,
+    /// Values above 1 soften the targets, showing the student how the teacher ranks the wrong
+    /// outputs as well as the right one
+    pub temperature: T,
+    /// How much the distillation loss counts, between 0(only the hard labels) and 1(only the
+    /// teacher)
+    pub distillation_weight: T,
+}
+
+impl<T: RealField + Copy, N, A: Activator<T>> Distiller<T, N, A> {
+    /// Creates a distiller
+    pub fn new(teacher: N, activator: A, temperature: T, distillation_weight: T) -> Self {
+        Self {
+            teacher,
+            activator,
+            temperature,
+            distillation_weight,
+        }
+    }
+
+    /// The teacher's soft targets for inputs
+    pub fn soft_targets<const INPUTS: usize, const OUTPUTS: usize>(
+        &self,
+        inputs: SVector<T, INPUTS>,
+    ) -> SVector<T, OUTPUTS>
+    where
+        N: Network<T, INPUTS, OUTPUTS>,
+    {
+        let logits = self.teacher.evaluate(inputs, &self.activator);
+        Softmax.evaluate(logits / self.temperature, &Linear)
+    }
+
+    /// Perform 1 training epoch on student. Each sample's loss is `distillation_weight` times
+    /// `temperature²` times the KL divergence of the student's softened outputs from the soft
+    /// targets, plus `1 - distillation_weight` times loss_function of the student's outputs and
+    /// the expected outputs. The temperature² keeps the distillation gradient the same size
+    /// whatever the temperature. Returns the average loss.
+    pub fn train<
+        'a,
+        S: TrainableNetwork<T, INPUTS, OUTPUTS>,
+        const INPUTS: usize,
+        const OUTPUTS: usize,
+    >(
+        &self,
+        data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+        student: &mut S,
+        activator: &impl Activator<T>,
+        loss_function: &(impl Loss<T, OUTPUTS> + ?Sized),
+        optimiser: &mut impl Optimiser<T, S::Gradient>,
+    ) -> Result<T, TrainError>
+    where
+        N: Network<T, INPUTS, OUTPUTS>,
+    {
+        let mut accumulator = Accumulator::new();
+        let mut total_loss = T::zero();
+
+        for (sample, (x, y)) in data.enumerate() {
+            let targets = self.soft_targets(*x);
+            let (predicted, layer_inputs) = student.evaluate_training(*x, activator);
+
+            let (hard_loss, hard_gradient) = loss_function.loss(y, &predicted);
+            let (soft_loss, soft_gradient) = self.distillation_loss(&targets, &predicted);
+
+            let hard_weight = T::one() - self.distillation_weight;
+            let instance_loss = soft_loss * self.distillation_weight + hard_loss * hard_weight;
+            if !instance_loss.is_finite() {
+                return Err(TrainError::NonFiniteLoss { sample });
+            }
+            total_loss += instance_loss;
+
+            let loss_gradient =
+                soft_gradient * self.distillation_weight + hard_gradient * hard_weight;
+            let (gradient, _) = student.get_gradient(&layer_inputs, loss_gradient, activator);
+            accumulator.add(&gradient);
+        }
+
+        let count = accumulator.count();
+
+        apply_accumulated(student, optimiser, &mut accumulator)?;
+
+        Ok(total_loss / nalgebra::convert(count as f64))
+    }
+
+    /// `temperature²` times the KL divergence of the softened logits from targets, and its
+    /// gradient with respect to the logits
+    fn distillation_loss<const OUTPUTS: usize>(
+        &self,
+        targets: &SVector<T, OUTPUTS>,
+        logits: &SVector<T, OUTPUTS>,
+    ) -> (T, SVector<T, OUTPUTS>) {
+        let predicted = Softmax.evaluate(logits / self.temperature, &Linear);
+
+        // 0 * ln(0) is 0, so targets of 0 don't add to the divergence
+        let divergence = targets
+            .iter()
+            .zip(predicted.iter())
+            .filter(|(&t, _)| t > T::zero())
+            .fold(T::zero(), |total, (&t, &p)| total + t * (t.ln() - p.ln()));
+
+        (
+            divergence * self.temperature * self.temperature,
+            (predicted - targets) * self.temperature,
+        )
+    }
+}
+
+/// Tests
+mod test {
+    #[test]
+    fn distillation_loss_test() {
+        use super::Distiller;
+        use crate::{activators::Linear, operations::Exp};
+        use nalgebra::Vector3;
+
+        let distiller = Distiller::new(Exp, Linear, 2f64, 0.5);
+        let logits = Vector3::new(0.3, -1., 2.);
+        let targets = distiller.soft_targets(Vector3::new(0., 0.5, 1.));
+        assert!((targets.sum() - 1.).abs() < 1e-12);
+
+        // matching the teacher exactly gives no loss
+        let (loss, gradient) = distiller.distillation_loss(&targets, &targets.map(|t| t.ln() * 2.));
+        assert!(loss.abs() < 1e-12 && gradient.norm() < 1e-12);
+
+        let (loss, gradient) = distiller.distillation_loss(&targets, &logits);
+        let step = 1e-6;
+        for i in 0..3 {
+            let mut nudged = logits;
+            nudged[i] += step;
+            let numerical = (distiller.distillation_loss(&targets, &nudged).0 - loss) / step;
+            assert!((numerical - gradient[i]).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{activators::Activator, network::TrainableNetwork, random::standard_normal};
+use nalgebra::{RealField, SVector};
+use rand::Rng;
+
+/// The gradient of `output_gradients . outputs` with respect to each input, i.e. how much each
+/// input affects the outputs, weighted by output_gradients. The other functions in this module
+/// are built on this.
+pub fn input_gradient<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    activator: &impl Activator<T>,
+    output_gradients: SVector<T, OUTPUTS>,
+) -> SVector<T, INPUTS> {
+    let (_, layer_inputs) = network.evaluate_training(inputs, activator);
+
+    network
+        .get_gradient(&layer_inputs, output_gradients, activator)
+        .1
+}
+
+/// Vanilla saliency, the gradient of outputs[output] with respect to each input
+/// # Panics
+/// If output isn't less than `OUTPUTS`
+pub fn saliency<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    activator: &impl Activator<T>,
+    output: usize,
+) -> SVector<T, INPUTS> {
+    let mut output_gradients = SVector::zeros();
+    output_gradients[output] = T::one();
+
+    input_gradient(network, inputs, activator, output_gradients)
+}
+
+/// The [saliency] multiplied by each input, which accounts for how large each input is
+/// # Panics
+/// If output isn't less than `OUTPUTS`
+pub fn gradient_times_input<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    activator: &impl Activator<T>,
+    output: usize,
+) -> SVector<T, INPUTS> {
+    saliency(network, inputs, activator, output).component_mul(&inputs)
+}
+
+/// Integrated gradients, the [saliency] averaged along the straight line from baseline(e.g. all
+/// zeros, or a typical "nothing happening" reading) to inputs, multiplied by `inputs - baseline`.
+/// The attributions sum to roughly `outputs[output]` minus the baseline's, getting closer as steps
+/// (the number of points on the line, at least 1) increases.
+/// # Panics
+/// If output isn't less than `OUTPUTS`
+pub fn integrated_gradients<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    baseline: SVector<T, INPUTS>,
+    activator: &impl Activator<T>,
+    output: usize,
+    steps: usize,
+) -> SVector<T, INPUTS> {
+    let steps = steps.max(1);
+    let difference = inputs - baseline;
+
+    // the midpoint of each step is more accurate than either end
+    let total = (0..steps).fold(SVector::zeros(), |total, step| {
+        let position: T = nalgebra::convert((step as f64 + 0.5) / steps as f64);
+        total + saliency(network, baseline + difference * position, activator, output)
+    });
+
+    total.component_mul(&difference) / nalgebra::convert::<f64, T>(steps as f64)
+}
+
+/// SmoothGrad, the [saliency] averaged over samples(at least 1) copies of inputs with normally
+/// distributed noise added, which reduces the noise in the saliency itself
+/// # Panics
+/// If output isn't less than `OUTPUTS`
+pub fn smooth_grad<
+    T: RealField + Copy,
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    network: &N,
+    inputs: SVector<T, INPUTS>,
+    activator: &impl Activator<T>,
+    output: usize,
+    samples: usize,
+    noise_deviation: T,
+    rng: &mut impl Rng,
+) -> SVector<T, INPUTS> {
+    let samples = samples.max(1);
+
+    let total = (0..samples).fold(SVector::zeros(), |total, _| {
+        let noisy = inputs + standard_normal::<T, INPUTS>(rng) * noise_deviation;
+        total + saliency(network, noisy, activator, output)
+    });
+
+    total / nalgebra::convert::<f64, T>(samples as f64)
+}
+
+/// Tests
+mod test {
+    #[test]
+    fn explain_test() {
+        use super::{gradient_times_input, integrated_gradients, saliency, smooth_grad};
+        use crate::{activators::Linear, operations::Exp};
+        use nalgebra::Vector3;
+        use rand::rngs::mock::StepRng;
+
+        // each output only depends on its input, so only that input should be salient
+        let inputs = Vector3::new(1f64, 0.5, -2.);
+        let salient = saliency(&Exp, inputs, &Linear, 1);
+        assert_eq!(salient, Vector3::new(0., 0.5f64.exp(), 0.));
+        assert_eq!(
+            gradient_times_input(&Exp, inputs, &Linear, 1),
+            salient * 0.5
+        );
+
+        // completeness: the attributions sum to the change in output from the baseline
+        let attributions = integrated_gradients(&Exp, inputs, Vector3::zeros(), &Linear, 2, 50);
+        assert!((attributions.sum() - ((-2f64).exp() - 1.)).abs() < 1e-4);
+
+        // without noise, SmoothGrad is just the saliency
+        let smooth = smooth_grad(
+            &Exp,
+            inputs,
+            &Linear,
+            1,
+            4,
+            0.,
+            &mut StepRng::new(1, 1 << 60),
+        );
+        assert!((smooth - salient).norm() < 1e-12);
+    }
+}
@@ -0,0 +1,450 @@
+extern crate std;
+
+use crate::{activators::Activator, backprop::TrainError, data::Dataset, network::Network};
+use nalgebra::{RealField, SVector};
+use std::{vec, vec::Vec};
+
+/// How per-class scores are combined into one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Average {
+    /// Compute the score for each class, then take the unweighted mean
+    Macro,
+    /// Compute the score from the counts pooled across every class
+    Micro,
+}
+
+/// Counts of how often each class(row) was predicted as each class(column)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    /// `counts[actual][predicted]`
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl ConfusionMatrix {
+    /// The number of classes
+    pub fn classes(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// The number of samples counted
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// The proportion of samples predicted correctly
+    pub fn accuracy(&self) -> f64 {
+        let correct: usize = (0..self.classes()).map(|c| self.counts[c][c]).sum();
+        ratio(correct, self.total())
+    }
+
+    /// The proportion of samples predicted as class that actually were that class
+    pub fn precision(&self, class: usize) -> f64 {
+        let predicted: usize = self.counts.iter().map(|row| row[class]).sum();
+        ratio(self.counts[class][class], predicted)
+    }
+
+    /// The proportion of samples of class that were predicted as that class
+    pub fn recall(&self, class: usize) -> f64 {
+        ratio(self.counts[class][class], self.counts[class].iter().sum())
+    }
+
+    /// The harmonic mean of the precision and recall of class
+    pub fn f1(&self, class: usize) -> f64 {
+        harmonic_mean(self.precision(class), self.recall(class))
+    }
+
+    /// The precision, averaged across classes
+    pub fn average_precision(&self, average: Average) -> f64 {
+        match average {
+            Average::Macro => self.macro_average(Self::precision),
+            // every false positive for one class is a false negative for another, so micro
+            // averaged precision, recall and f1 all equal accuracy
+            Average::Micro => self.accuracy(),
+        }
+    }
+
+    /// The recall, averaged across classes
+    pub fn average_recall(&self, average: Average) -> f64 {
+        match average {
+            Average::Macro => self.macro_average(Self::recall),
+            Average::Micro => self.accuracy(),
+        }
+    }
+
+    /// The F1 score, averaged across classes
+    pub fn average_f1(&self, average: Average) -> f64 {
+        match average {
+            Average::Macro => self.macro_average(Self::f1),
+            Average::Micro => self.accuracy(),
+        }
+    }
+
+    /// The mean of score across every class
+    fn macro_average(&self, score: fn(&Self, usize) -> f64) -> f64 {
+        let total: f64 = (0..self.classes()).map(|c| score(self, c)).sum();
+        total / self.classes() as f64
+    }
+}
+
+/// Builds the confusion matrix of a classifier. Classes are the index of the largest output,
+/// or for networks with a single output, whether it is at least 0.5.
+pub fn confusion_matrix<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+) -> ConfusionMatrix {
+    let classes = OUTPUTS.max(2);
+    let mut counts = vec![vec![0; classes]; classes];
+
+    for (input, expected) in data {
+        let predicted = network.evaluate(*input, activator);
+        counts[class_of(expected)][class_of(&predicted)] += 1;
+    }
+
+    ConfusionMatrix { counts }
+}
+
+/// The proportion of samples a classifier predicts correctly, see [confusion_matrix], or
+/// [TrainError::EmptyDataset] if there's no data
+pub fn accuracy<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+) -> Result<T, TrainError> {
+    let matrix = confusion_matrix(data, network, activator);
+    if matrix.total() == 0 {
+        return Err(TrainError::EmptyDataset);
+    }
+
+    Ok(nalgebra::convert(matrix.accuracy()))
+}
+
+/// The proportion of samples whose class is among the k largest outputs, or
+/// [TrainError::EmptyDataset] if there's no data. Classes are found like [confusion_matrix], so
+/// a network with a single output has 2 classes, and only its predicted class is in the top 1.
+pub fn top_k_accuracy<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+    k: usize,
+) -> Result<T, TrainError> {
+    let (mut correct, mut total) = (0, 0);
+
+    for (input, expected) in data {
+        let predicted = network.evaluate(*input, activator);
+        let class = class_of(expected);
+
+        // the class is in the top k if fewer than k classes beat it
+        let rank = if OUTPUTS == 1 {
+            (class != class_of(&predicted)) as usize
+        } else {
+            predicted
+                .iter()
+                .enumerate()
+                .filter(|&(i, &v)| v > predicted[class] || (v == predicted[class] && i < class))
+                .count()
+        };
+
+        total += 1;
+        if rank < k {
+            correct += 1;
+        }
+    }
+
+    if total == 0 {
+        return Err(TrainError::EmptyDataset);
+    }
+    Ok(nalgebra::convert(ratio(correct, total)))
+}
+
+/// The area under the ROC curve of a binary classifier, using `output` as the score and
+/// treating samples whose expected `output` is at least 0.5 as positive. Returns None if
+/// data doesn't contain both positive and negative samples.
+/// # Panics
+/// If output isn't less than `OUTPUTS`
+pub fn roc_auc<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+    output: usize,
+) -> Option<T> {
+    assert!(output < OUTPUTS, "output {} is out of range", output);
+
+    let half: T = nalgebra::convert(0.5);
+    let mut scored: Vec<(T, bool)> = data
+        .map(|(input, expected)| {
+            let predicted = network.evaluate(*input, activator);
+            (predicted[output], expected[output] >= half)
+        })
+        .collect();
+    scored.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+
+    // The Mann-Whitney U statistic, with tied scores sharing their average rank
+    let mut positive_rank_sum = 0f64;
+    let mut start = 0;
+    while start < scored.len() {
+        let end = start
+            + scored[start..]
+                .iter()
+                .take_while(|(score, _)| *score == scored[start].0)
+                .count();
+        let average_rank = (start + end + 1) as f64 / 2.;
+        let positives = scored[start..end].iter().filter(|(_, p)| *p).count();
+
+        positive_rank_sum += average_rank * positives as f64;
+        start = end;
+    }
+
+    let positives = scored.iter().filter(|(_, p)| *p).count() as f64;
+    let negatives = scored.len() as f64 - positives;
+    if positives == 0. || negatives == 0. {
+        return None;
+    }
+
+    let u = positive_rank_sum - positives * (positives + 1.) / 2.;
+    Some(nalgebra::convert(u / (positives * negatives)))
+}
+
+/// The mean absolute difference between expected and predicted outputs, or
+/// [TrainError::EmptyDataset] if there's no data
+pub fn mean_absolute_error<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+) -> Result<T, TrainError> {
+    let (sum, count) = residuals(data, network, activator)?
+        .iter()
+        .fold((T::zero(), T::zero()), |(sum, count), (_, residual)| {
+            (sum + residual.abs().sum(), count + T::one())
+        });
+
+    Ok(sum / (count * nalgebra::convert(OUTPUTS as f64)))
+}
+
+/// The square root of the mean squared difference between expected and predicted outputs, or
+/// [TrainError::EmptyDataset] if there's no data
+pub fn root_mean_squared_error<
+    'a,
+    T: RealField + Copy,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+) -> Result<T, TrainError> {
+    let (sum, count) = residuals(data, network, activator)?
+        .iter()
+        .fold((T::zero(), T::zero()), |(sum, count), (_, residual)| {
+            (sum + residual.norm_squared(), count + T::one())
+        });
+
+    Ok((sum / (count * nalgebra::convert(OUTPUTS as f64))).sqrt())
+}
+
+/// The coefficient of determination(R²) of a regression model, averaged uniformly across outputs,
+/// or [TrainError::EmptyDataset] if there's no data. An output whose expected value never changes
+/// scores 1 if it's predicted exactly, and 0 otherwise.
+pub fn r_squared<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+) -> Result<T, TrainError> {
+    let residuals = residuals(data, network, activator)?;
+
+    let count: T = nalgebra::convert(residuals.len() as f64);
+    let mean = residuals
+        .iter()
+        .fold(SVector::<T, OUTPUTS>::zeros(), |sum, (expected, _)| {
+            sum + expected
+        })
+        / count;
+
+    let (residual_squares, total_squares) = residuals.iter().fold(
+        (
+            SVector::<T, OUTPUTS>::zeros(),
+            SVector::<T, OUTPUTS>::zeros(),
+        ),
+        |(residual_squares, total_squares), (expected, residual)| {
+            let deviation = expected - mean;
+            (
+                residual_squares + residual.component_mul(residual),
+                total_squares + deviation.component_mul(&deviation),
+            )
+        },
+    );
+
+    let scores = SVector::<T, OUTPUTS>::from_fn(|i, _| {
+        match (total_squares[i].is_zero(), residual_squares[i].is_zero()) {
+            (false, _) => T::one() - residual_squares[i] / total_squares[i],
+            (true, true) => T::one(),
+            (true, false) => T::zero(),
+        }
+    });
+    Ok(scores.mean())
+}
+
+/// Evaluates the network on data, returning each `(expected, expected - predicted)` pair, or
+/// [TrainError::EmptyDataset] if there are none
+fn residuals<'a, T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(
+    data: impl Iterator<Item = &'a (SVector<T, INPUTS>, SVector<T, OUTPUTS>)>,
+    network: &impl Network<T, INPUTS, OUTPUTS>,
+    activator: &impl Activator<T>,
+) -> Result<Dataset<T, OUTPUTS, OUTPUTS>, TrainError> {
+    let residuals: Vec<_> = data
+        .map(|(input, expected)| (*expected, expected - network.evaluate(*input, activator)))
+        .collect();
+
+    if residuals.is_empty() {
+        Err(TrainError::EmptyDataset)
+    } else {
+        Ok(residuals)
+    }
+}
+
+/// The class represented by outputs, the largest output or, for single outputs, whether it is at least 0.5
+fn class_of<T: RealField + Copy, const OUTPUTS: usize>(outputs: &SVector<T, OUTPUTS>) -> usize {
+    if OUTPUTS == 1 {
+        (outputs[0] >= nalgebra::convert(0.5)) as usize
+    } else {
+        outputs.imax()
+    }
+}
+
+/// numerator / denominator, or 0 if denominator is 0
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// The harmonic mean of a and b, or 0 if both are 0
+fn harmonic_mean(a: f64, b: f64) -> f64 {
+    if a + b == 0. {
+        0.
+    } else {
+        2. * a * b / (a + b)
+    }
+}
+
+/// Tests
+mod test {
+    /// Outputs its inputs
+    #[cfg(test)]
+    struct Identity;
+
+    #[cfg(test)]
+    impl<const N: usize> crate::Network<f64, N, N> for Identity {
+        fn evaluate(
+            &self,
+            inputs: nalgebra::SVector<f64, N>,
+            _: &impl crate::activators::Activator<f64>,
+        ) -> nalgebra::SVector<f64, N> {
+            inputs
+        }
+    }
+
+    #[test]
+    fn metrics_test() {
+        use super::{
+            accuracy, confusion_matrix, mean_absolute_error, r_squared, roc_auc,
+            root_mean_squared_error, top_k_accuracy, Average,
+        };
+        use crate::{activators::Linear, backprop::TrainError};
+        use nalgebra::{Vector1, Vector3};
+
+        // Identity "predicts" its input, the second vector is the expected class
+        let data = [
+            (Vector3::new(0.7, 0.2, 0.1), Vector3::new(1., 0., 0.)),
+            (Vector3::new(0.1, 0.8, 0.1), Vector3::new(0., 1., 0.)),
+            (Vector3::new(0.5, 0.4, 0.1), Vector3::new(0., 1., 0.)),
+            (Vector3::new(0.1, 0.2, 0.7), Vector3::new(0., 0., 1.)),
+        ];
+        let matrix = confusion_matrix(data.iter(), &Identity, &Linear);
+        assert_eq!(matrix.counts[1], [1, 1, 0]);
+        assert_eq!(accuracy(data.iter(), &Identity, &Linear), Ok(0.75));
+        assert_eq!(top_k_accuracy(data.iter(), &Identity, &Linear, 2), Ok(1.));
+        assert_eq!(top_k_accuracy(data.iter(), &Identity, &Linear, 1), Ok(0.75));
+        assert_eq!(matrix.precision(0), 0.5);
+        assert_eq!(matrix.recall(1), 0.5);
+        assert!((matrix.average_f1(Average::Macro) - (2. / 3. + 2. / 3. + 1.) / 3.).abs() < 1e-9);
+        assert_eq!(matrix.average_f1(Average::Micro), 0.75);
+
+        let binary = [
+            (Vector1::new(0.1), Vector1::new(0.)),
+            (Vector1::new(0.4), Vector1::new(1.)),
+            (Vector1::new(0.35), Vector1::new(0.)),
+            (Vector1::new(0.8), Vector1::new(1.)),
+        ];
+        assert_eq!(roc_auc(binary.iter(), &Identity, &Linear, 0), Some(1.));
+        // 0.4 is predicted as class 0, so only 3 of the single output samples are in the top 1
+        assert_eq!(
+            top_k_accuracy(binary.iter(), &Identity, &Linear, 1),
+            Ok(0.75)
+        );
+        assert_eq!(top_k_accuracy(binary.iter(), &Identity, &Linear, 2), Ok(1.));
+
+        let regression = [
+            (Vector1::new(1.), Vector1::new(2.)),
+            (Vector1::new(3.), Vector1::new(3.)),
+            (Vector1::new(4.), Vector1::new(4.)),
+        ];
+        assert_eq!(
+            mean_absolute_error(regression.iter(), &Identity, &Linear),
+            Ok(1. / 3.)
+        );
+        assert_eq!(
+            root_mean_squared_error(regression.iter(), &Identity, &Linear),
+            Ok((1f64 / 3.).sqrt())
+        );
+        assert_eq!(r_squared(regression.iter(), &Identity, &Linear), Ok(0.5));
+
+        let empty: [(Vector1<f64>, Vector1<f64>); 0] = [];
+        assert_eq!(
+            accuracy(empty.iter(), &Identity, &Linear),
+            Err(TrainError::EmptyDataset)
+        );
+        assert_eq!(
+            top_k_accuracy(empty.iter(), &Identity, &Linear, 1),
+            Err(TrainError::EmptyDataset)
+        );
+        assert_eq!(
+            mean_absolute_error(empty.iter(), &Identity, &Linear),
+            Err(TrainError::EmptyDataset)
+        );
+        assert_eq!(
+            root_mean_squared_error(empty.iter(), &Identity, &Linear),
+            Err(TrainError::EmptyDataset)
+        );
+        assert_eq!(
+            r_squared(empty.iter(), &Identity, &Linear),
+            Err(TrainError::EmptyDataset)
+        );
+
+        // The first output is always 1 and predicted exactly, the second is always 1 but isn't
+        let constant = [
+            (
+                nalgebra::Vector2::new(1., 0.),
+                nalgebra::Vector2::new(1., 1.),
+            ),
+            (
+                nalgebra::Vector2::new(1., 2.),
+                nalgebra::Vector2::new(1., 1.),
+            ),
+        ];
+        assert_eq!(r_squared(constant.iter(), &Identity, &Linear), Ok(0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn roc_auc_range_test() {
+        use super::roc_auc;
+        use crate::activators::Linear;
+        use nalgebra::Vector1;
+
+        let binary = [(Vector1::new(0.1), Vector1::new(0.))];
+        roc_auc(binary.iter(), &Identity, &Linear, 1);
+    }
+}
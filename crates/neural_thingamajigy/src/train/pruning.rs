@@ -0,0 +1,228 @@
+extern crate std;
+
+use crate::{
+    activators::Activator, BatchTrainableNetwork, LayerInfo, Network, NetworkInfo,
+    TrainableNetwork, ValueSet,
+};
+use core::cmp::Ordering;
+use nalgebra::{RealField, SMatrix, SVector};
+use std::{vec, vec::Vec};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A network whose weights can be pruned. This is implemented by `network!`.
+pub trait PrunableNetwork<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>:
+    TrainableNetwork<T, INPUTS, OUTPUTS>
+{
+    /// Calls f with the index of the layer and every weight of that layer
+    fn inspect_weights(&self, f: &mut impl FnMut(usize, &T));
+
+    /// Replaces every weight with f(layer index, weight), in the same order as
+    /// [Self::inspect_weights]
+    fn map_weights(&mut self, f: &mut impl FnMut(usize, &T) -> T);
+
+    /// A gradient shaped mask, 1 for every bias and non-zero weight, and 0 for every pruned(zero)
+    /// weight
+    fn mask(&self) -> Self::Gradient;
+
+    /// The proportion of weights that are zero
+    fn sparsity(&self) -> f64 {
+        let (mut zeros, mut total) = (0usize, 0usize);
+        self.inspect_weights(&mut |_, w| {
+            zeros += usize::from(*w == T::zero());
+            total += 1;
+        });
+
+        if total == 0 {
+            0.
+        } else {
+            zeros as f64 / total as f64
+        }
+    }
+}
+
+/// Which weights are compared when choosing the smallest to prune
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PruneScope {
+    /// Prune the smallest weights of the whole network, so layers with many small weights are
+    /// pruned more
+    Global,
+    /// Prune the smallest weights of each layer, so every layer has the same sparsity
+    PerLayer,
+}
+
+/// Zeros the smallest magnitude weights of network, so that at least the sparsity(between 0 and
+/// 1) proportion of them are zero, and wraps it so further training keeps them at zero.
+pub fn prune<
+    T: RealField + Copy,
+    N: PrunableNetwork<T, INPUTS, OUTPUTS>,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+>(
+    mut network: N,
+    sparsity: f64,
+    scope: PruneScope,
+) -> Pruned<N, N::Gradient> {
+    let group = |layer: usize| match scope {
+        PruneScope::Global => 0,
+        PruneScope::PerLayer => layer,
+    };
+
+    let mut magnitudes: Vec<Vec<T>> = Vec::new();
+    network.inspect_weights(&mut |layer, w| {
+        let group = group(layer);
+        if magnitudes.len() <= group {
+            magnitudes.resize(group + 1, Vec::new());
+        }
+        magnitudes[group].push(w.abs());
+    });
+
+    // prune everything below each group's threshold, and as many weights equal to it as needed
+    let mut thresholds = vec![None; magnitudes.len()];
+    let mut ties = vec![0; magnitudes.len()];
+    for (group, mut magnitudes) in magnitudes.into_iter().enumerate() {
+        let count = (sparsity.clamp(0., 1.) * magnitudes.len() as f64).ceil() as usize;
+        if count == 0 {
+            continue;
+        }
+
+        magnitudes.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let threshold = magnitudes[count - 1];
+        thresholds[group] = Some(threshold);
+        ties[group] = count - magnitudes.iter().filter(|&&m| m < threshold).count();
+    }
+
+    network.map_weights(&mut |layer, &w| {
+        let group = group(layer);
+        match thresholds[group] {
+            Some(threshold) if w.abs() < threshold => T::zero(),
+            Some(threshold) if w.abs() == threshold && ties[group] > 0 => {
+                ties[group] -= 1;
+                T::zero()
+            }
+            _ => w,
+        }
+    });
+
+    Pruned::new(network)
+}
+
+/// A network whose zero weights stay zero while training, because every nudge is multiplied by
+/// a mask. Create with [prune], or [Pruned::new] to keep the zeros of an already pruned network.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct Pruned<N, G> {
+    /// The pruned network
+    pub network: N,
+    /// The mask that nudges are multiplied by, see [PrunableNetwork::mask]
+    pub mask: G,
+}
+
+impl<N, G> Pruned<N, G> {
+    /// Creates a pruned network, keeping every weight that is currently zero at zero
+    pub fn new<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize>(network: N) -> Self
+    where
+        N: PrunableNetwork<T, INPUTS, OUTPUTS, Gradient = G>,
+    {
+        Self {
+            mask: network.mask(),
+            network,
+        }
+    }
+
+    /// Returns the pruned network
+    pub fn into_inner(self) -> N {
+        self.network
+    }
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize, N, G>
+    Network<T, INPUTS, OUTPUTS> for Pruned<N, G>
+where
+    N: Network<T, INPUTS, OUTPUTS>,
+{
+    fn evaluate(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, OUTPUTS> {
+        self.network.evaluate(inputs, activator)
+    }
+
+    fn evaluate_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> SMatrix<T, OUTPUTS, B> {
+        self.network.evaluate_batch(inputs, activator)
+    }
+}
+
+/// Describes the pruned network, whose pruned weights are still stored so are counted
+impl<const INPUTS: usize, const OUTPUTS: usize, N: NetworkInfo<INPUTS, OUTPUTS>, G>
+    NetworkInfo<INPUTS, OUTPUTS> for Pruned<N, G>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        self.network.layer_info()
+    }
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize, N>
+    TrainableNetwork<T, INPUTS, OUTPUTS> for Pruned<N, N::Gradient>
+where
+    N: TrainableNetwork<T, INPUTS, OUTPUTS>,
+{
+    type LayerInputs = N::LayerInputs;
+
+    type Gradient = N::Gradient;
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, OUTPUTS>, Self::LayerInputs) {
+        self.network.evaluate_training(inputs, activator)
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, OUTPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SVector<T, INPUTS>) {
+        self.network
+            .get_gradient(layer_inputs, output_loss_gradients, activator)
+    }
+
+    fn apply_nudge(&mut self, nudge: Self::Gradient) {
+        self.network
+            .apply_nudge(nudge.binary_operation(&self.mask, |&n, &m| n * m));
+    }
+}
+
+impl<T: RealField + Copy, const INPUTS: usize, const OUTPUTS: usize, N>
+    BatchTrainableNetwork<T, INPUTS, OUTPUTS> for Pruned<N, N::Gradient>
+where
+    N: BatchTrainableNetwork<T, INPUTS, OUTPUTS>,
+{
+    type BatchLayerInputs<const B: usize> = N::BatchLayerInputs<B>;
+
+    fn evaluate_training_batch<const B: usize>(
+        &self,
+        inputs: SMatrix<T, INPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (SMatrix<T, OUTPUTS, B>, Self::BatchLayerInputs<B>) {
+        self.network.evaluate_training_batch(inputs, activator)
+    }
+
+    fn get_gradient_batch<const B: usize>(
+        &self,
+        layer_inputs: &Self::BatchLayerInputs<B>,
+        output_loss_gradients: SMatrix<T, OUTPUTS, B>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SMatrix<T, INPUTS, B>) {
+        self.network
+            .get_gradient_batch(layer_inputs, output_loss_gradients, activator)
+    }
+}
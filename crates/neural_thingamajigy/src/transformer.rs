@@ -0,0 +1,70 @@
+use nalgebra::{RealField, SMatrix, SVector};
+
+/// Defines MultiHeadSelfAttention
+mod attention;
+/// Defines FeedForward and TransformerEncoderBlock
+mod encoder;
+
+pub use {
+    attention::MultiHeadSelfAttention,
+    encoder::{FeedForward, TransformerEncoderBlock},
+};
+
+/// Applies f to every token(column) of a sequence
+fn map_tokens<T: RealField + Copy, const IN: usize, const OUT: usize, const SEQ: usize>(
+    tokens: &SMatrix<T, IN, SEQ>,
+    f: impl Fn(SVector<T, IN>) -> SVector<T, OUT>,
+) -> SMatrix<T, OUT, SEQ> {
+    let mut mapped = SMatrix::<T, OUT, SEQ>::zeros();
+    for (i, token) in tokens.column_iter().enumerate() {
+        mapped.set_column(i, &f(token.into_owned()));
+    }
+
+    mapped
+}
+
+/// Tests
+#[cfg(feature = "std-train")]
+mod test {
+    #[test]
+    fn encoder_gradient_test() {
+        use super::TransformerEncoderBlock;
+        use crate::{activators::Elu, Network, RandomisableNetwork, TrainableNetwork, ValueSet};
+        use nalgebra::SVector;
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha8Rng;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut block = TransformerEncoderBlock::<f64, 3, 4, 2, 5>::random(&mut rng);
+        let inputs = SVector::<f64, 12>::from_fn(|_, _| rng.gen_range(-1.0..1.0));
+        let loss_gradients = SVector::<f64, 12>::from_fn(|_, _| rng.gen_range(-1.0..1.0));
+
+        // the loss is loss_gradients . outputs, so its gradient is loss_gradients
+        let loss = |block: &TransformerEncoderBlock<f64, 3, 4, 2, 5>, inputs| {
+            loss_gradients.dot(&block.evaluate(inputs, &Elu))
+        };
+
+        let (outputs, layer_inputs) = block.evaluate_training(inputs, &Elu);
+        assert_eq!(outputs, block.evaluate(inputs, &Elu));
+        let (gradient, input_gradients) = block.get_gradient(&layer_inputs, loss_gradients, &Elu);
+
+        let step = 1e-6;
+        for i in 0..12 {
+            let mut nudged = inputs;
+            nudged[i] += step;
+            let numerical = (loss(&block, nudged) - loss(&block, inputs)) / step;
+            assert!((numerical - input_gradients[i]).abs() < 1e-4);
+        }
+
+        // nudging by a small step of the gradient should increase the loss by step * |gradient|^2
+        let mut squared_norm = 0.;
+        gradient.unary_inspection(&mut |g| squared_norm += g * g);
+        let before = loss(&block, inputs);
+        TrainableNetwork::<f64, 12, 12>::apply_nudge(
+            &mut block,
+            gradient.unary_operation(|g| g * step),
+        );
+        let numerical = (loss(&block, inputs) - before) / step;
+        assert!((numerical - squared_norm).abs() / squared_norm < 1e-3);
+    }
+}
@@ -0,0 +1,329 @@
+use nalgebra::{RealField, SMatrix, SVector};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::map_tokens;
+use crate::{
+    activators::{Activator, Linear},
+    operations::Softmax,
+    Layer, LayerInfo, Network, NetworkInfo,
+};
+
+/// Multi-head scaled dot-product self attention over a sequence of `SEQ` tokens, each with `DIM`
+/// values. The inputs and outputs are the tokens one after another, so the network has
+/// `SEQ * DIM` inputs and outputs. `DIM` is split evenly between `HEADS` heads.
+///
+/// The query, key, value and output projections are linear, so the activator is unused.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiHeadSelfAttention<
+    T: RealField + Copy,
+    const SEQ: usize,
+    const DIM: usize,
+    const HEADS: usize,
+> {
+    /// Projects each token to its queries
+    pub query: Layer<T, DIM, DIM>,
+    /// Projects each token to its keys
+    pub key: Layer<T, DIM, DIM>,
+    /// Projects each token to its values
+    pub value: Layer<T, DIM, DIM>,
+    /// Projects the concatenated output of each head
+    pub output: Layer<T, DIM, DIM>,
+}
+
+/// Every intermediate value of attending to a sequence
+#[cfg_attr(not(feature = "backprop"), allow(dead_code))] // only training needs more than the outputs
+struct Attended<T: RealField, const SEQ: usize, const DIM: usize, const HEADS: usize> {
+    /// The queries of each token
+    queries: SMatrix<T, DIM, SEQ>,
+    /// The keys of each token
+    keys: SMatrix<T, DIM, SEQ>,
+    /// The values of each token
+    values: SMatrix<T, DIM, SEQ>,
+    /// For each head, the scaled dot product of every key(row) with every query(column)
+    scores: [SMatrix<T, SEQ, SEQ>; HEADS],
+    /// For each head, the softmaxed scores, so each column sums to 1
+    weights: [SMatrix<T, SEQ, SEQ>; HEADS],
+    /// The values mixed by each head's weights, before the output projection
+    mixed: SMatrix<T, DIM, SEQ>,
+    /// The outputs of each token
+    outputs: SMatrix<T, DIM, SEQ>,
+}
+
+impl<T: RealField + Copy, const SEQ: usize, const DIM: usize, const HEADS: usize>
+    MultiHeadSelfAttention<T, SEQ, DIM, HEADS>
+{
+    /// The number of values each head attends with
+    const HEAD_DIM: usize = {
+        assert!(
+            HEADS > 0 && DIM.is_multiple_of(HEADS),
+            "DIM must be divisible by HEADS"
+        );
+        DIM / HEADS
+    };
+
+    /// Panics at compile time if LEN isn't `SEQ * DIM`
+    const fn check_length<const LEN: usize>() {
+        assert!(LEN == SEQ * DIM, "attention inputs must be SEQ * DIM");
+    }
+
+    /// The scale applied to the scores, 1 / sqrt(HEAD_DIM)
+    fn scale() -> T {
+        T::one() / nalgebra::convert::<f64, T>(Self::HEAD_DIM as f64).sqrt()
+    }
+
+    /// Attends to a sequence of tokens, keeping every intermediate value
+    fn attend(&self, tokens: &SMatrix<T, DIM, SEQ>) -> Attended<T, SEQ, DIM, HEADS> {
+        let project =
+            |layer: &Layer<T, DIM, DIM>| map_tokens(tokens, |t| layer.through(t, &Linear));
+        let (queries, keys, values) = (
+            project(&self.query),
+            project(&self.key),
+            project(&self.value),
+        );
+
+        let mut scores = [SMatrix::<T, SEQ, SEQ>::zeros(); HEADS];
+        let mut weights = [SMatrix::<T, SEQ, SEQ>::zeros(); HEADS];
+        let mut mixed = SMatrix::<T, DIM, SEQ>::zeros();
+
+        for head in 0..HEADS {
+            let rows = head * Self::HEAD_DIM;
+
+            scores[head].gemm_tr(
+                Self::scale(),
+                &keys.rows(rows, Self::HEAD_DIM),
+                &queries.rows(rows, Self::HEAD_DIM),
+                T::zero(),
+            );
+            weights[head] = map_tokens(&scores[head], |s| Softmax.evaluate(s, &Linear));
+
+            mixed.rows_mut(rows, Self::HEAD_DIM).gemm(
+                T::one(),
+                &values.rows(rows, Self::HEAD_DIM),
+                &weights[head],
+                T::zero(),
+            );
+        }
+
+        let outputs = map_tokens(&mixed, |t| self.output.through(t, &Linear));
+
+        Attended {
+            queries,
+            keys,
+            values,
+            scores,
+            weights,
+            mixed,
+            outputs,
+        }
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const SEQ: usize,
+        const DIM: usize,
+        const HEADS: usize,
+        const LEN: usize,
+    > Network<T, LEN, LEN> for MultiHeadSelfAttention<T, SEQ, DIM, HEADS>
+{
+    fn evaluate(&self, inputs: SVector<T, LEN>, _: &impl Activator<T>) -> SVector<T, LEN> {
+        const { Self::check_length::<LEN>() };
+
+        let tokens = SMatrix::<T, DIM, SEQ>::from_column_slice(inputs.as_slice());
+        SVector::from_column_slice(self.attend(&tokens).outputs.as_slice())
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const SEQ: usize,
+        const DIM: usize,
+        const HEADS: usize,
+        const LEN: usize,
+    > NetworkInfo<LEN, LEN> for MultiHeadSelfAttention<T, SEQ, DIM, HEADS>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        // the query, key, value and output projections
+        let parameters = 4 * self.query.info("query").parameters;
+
+        core::iter::once(LayerInfo {
+            parameters,
+            trainable_parameters: parameters,
+            ..LayerInfo::operation("attention", "MultiHeadSelfAttention", LEN)
+        })
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::{LayerGradient, TrainableNetwork};
+#[cfg(feature = "random")]
+use {
+    crate::RandomisableNetwork,
+    rand::{distributions::Standard, prelude::Distribution, Rng},
+};
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const SEQ: usize,
+        const DIM: usize,
+        const HEADS: usize,
+        const LEN: usize,
+    > TrainableNetwork<T, LEN, LEN> for MultiHeadSelfAttention<T, SEQ, DIM, HEADS>
+{
+    // attending again is cheaper than storing every head's weights for every sample
+    type LayerInputs = SVector<T, LEN>;
+
+    /// The gradients of the (query, key) and (value, output) projections
+    type Gradient = (
+        (LayerGradient<T, DIM, DIM>, LayerGradient<T, DIM, DIM>),
+        (LayerGradient<T, DIM, DIM>, LayerGradient<T, DIM, DIM>),
+    );
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, LEN>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, LEN>, Self::LayerInputs) {
+        (self.evaluate(inputs, activator), inputs)
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, LEN>,
+        _: &impl Activator<T>,
+    ) -> (Self::Gradient, SVector<T, LEN>) {
+        let tokens = SMatrix::<T, DIM, SEQ>::from_column_slice(layer_inputs.as_slice());
+        let output_gradients =
+            SMatrix::<T, DIM, SEQ>::from_column_slice(output_loss_gradients.as_slice());
+        let attended = self.attend(&tokens);
+
+        let (output_gradient, mixed_gradients) =
+            backpropogate_tokens(&self.output, &attended.mixed, &output_gradients);
+
+        let mut query_gradients = SMatrix::<T, DIM, SEQ>::zeros();
+        let mut key_gradients = SMatrix::<T, DIM, SEQ>::zeros();
+        let mut value_gradients = SMatrix::<T, DIM, SEQ>::zeros();
+
+        for head in 0..HEADS {
+            let rows = head * Self::HEAD_DIM;
+            let head_mixed_gradients = mixed_gradients.rows(rows, Self::HEAD_DIM);
+
+            // mixed = values * weights
+            value_gradients.rows_mut(rows, Self::HEAD_DIM).gemm(
+                T::one(),
+                &head_mixed_gradients,
+                &attended.weights[head].transpose(),
+                T::zero(),
+            );
+            let mut weight_gradients = SMatrix::<T, SEQ, SEQ>::zeros();
+            weight_gradients.gemm_tr(
+                T::one(),
+                &attended.values.rows(rows, Self::HEAD_DIM),
+                &head_mixed_gradients,
+                T::zero(),
+            );
+
+            // each query's weights are a softmax of its scores
+            let mut score_gradients = SMatrix::<T, SEQ, SEQ>::zeros();
+            for i in 0..SEQ {
+                let (_, softmax_inputs) = Softmax
+                    .evaluate_training(attended.scores[head].column(i).into_owned(), &Linear);
+                let (_, gradient) = Softmax.get_gradient(
+                    &softmax_inputs,
+                    weight_gradients.column(i).into_owned(),
+                    &Linear,
+                );
+                score_gradients.set_column(i, &gradient);
+            }
+
+            // scores = scale * keys^T * queries
+            query_gradients.rows_mut(rows, Self::HEAD_DIM).gemm(
+                Self::scale(),
+                &attended.keys.rows(rows, Self::HEAD_DIM),
+                &score_gradients,
+                T::zero(),
+            );
+            key_gradients.rows_mut(rows, Self::HEAD_DIM).gemm(
+                Self::scale(),
+                &attended.queries.rows(rows, Self::HEAD_DIM),
+                &score_gradients.transpose(),
+                T::zero(),
+            );
+        }
+
+        let (query_gradient, from_queries) =
+            backpropogate_tokens(&self.query, &tokens, &query_gradients);
+        let (key_gradient, from_keys) = backpropogate_tokens(&self.key, &tokens, &key_gradients);
+        let (value_gradient, from_values) =
+            backpropogate_tokens(&self.value, &tokens, &value_gradients);
+
+        let input_gradients = from_queries + from_keys + from_values;
+
+        (
+            (
+                (query_gradient, key_gradient),
+                (value_gradient, output_gradient),
+            ),
+            SVector::from_column_slice(input_gradients.as_slice()),
+        )
+    }
+
+    fn apply_nudge(&mut self, nudge: Self::Gradient) {
+        let ((query, key), (value, output)) = nudge;
+
+        self.query
+            .apply_shifts(query.weight_gradient, query.bias_gradient);
+        self.key
+            .apply_shifts(key.weight_gradient, key.bias_gradient);
+        self.value
+            .apply_shifts(value.weight_gradient, value.bias_gradient);
+        self.output
+            .apply_shifts(output.weight_gradient, output.bias_gradient);
+    }
+}
+
+#[cfg(feature = "random")]
+impl<T: RealField + Copy, const SEQ: usize, const DIM: usize, const HEADS: usize>
+    RandomisableNetwork<T> for MultiHeadSelfAttention<T, SEQ, DIM, HEADS>
+where
+    Standard: Distribution<T>,
+{
+    fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            query: Layer::random(rng),
+            key: Layer::random(rng),
+            value: Layer::random(rng),
+            output: Layer::random(rng),
+        }
+    }
+}
+
+/// Backpropogates a linear layer applied to every token, returning the layer's gradient summed
+/// over every token and the gradient of each input token
+#[cfg(feature = "backprop")]
+fn backpropogate_tokens<T: RealField + Copy, const DIM: usize, const SEQ: usize>(
+    layer: &Layer<T, DIM, DIM>,
+    inputs: &SMatrix<T, DIM, SEQ>,
+    loss_gradients: &SMatrix<T, DIM, SEQ>,
+) -> (LayerGradient<T, DIM, DIM>, SMatrix<T, DIM, SEQ>) {
+    use crate::ValueSet;
+
+    let mut layer_gradient = LayerGradient::default();
+    let mut input_gradients = SMatrix::<T, DIM, SEQ>::zeros();
+
+    for i in 0..SEQ {
+        let (gradient, input_gradient) = layer.backpropogate(
+            loss_gradients.column(i).into_owned(),
+            inputs.column(i).into_owned(),
+            &Linear,
+        );
+        layer_gradient = layer_gradient.binary_operation(&gradient, |&a, &b| a + b);
+        input_gradients.set_column(i, &input_gradient);
+    }
+
+    (layer_gradient, input_gradients)
+}
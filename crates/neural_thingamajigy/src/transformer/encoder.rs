@@ -0,0 +1,345 @@
+use nalgebra::{RealField, SVector};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::MultiHeadSelfAttention;
+use crate::{
+    activators::{Activator, Linear},
+    operations::LayerNorm,
+    Layer, LayerInfo, Network, NetworkInfo,
+};
+
+/// A 2 layer network applied to each token of a sequence independently. The hidden layer uses the
+/// activator, and the output layer is linear. `INPUTS` must be a multiple of `DIM`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FeedForward<T: RealField + Copy, const DIM: usize, const HIDDEN: usize> {
+    /// The activated hidden layer
+    pub hidden: Layer<T, DIM, HIDDEN>,
+    /// The linear output layer
+    pub output: Layer<T, HIDDEN, DIM>,
+}
+
+impl<T: RealField + Copy, const DIM: usize, const HIDDEN: usize> FeedForward<T, DIM, HIDDEN> {
+    /// Panics at compile time if INPUTS isn't made of whole tokens
+    const fn check_width<const INPUTS: usize>() {
+        assert!(
+            DIM > 0 && INPUTS.is_multiple_of(DIM),
+            "FeedForward inputs must be a multiple of DIM"
+        );
+    }
+}
+
+impl<T: RealField + Copy, const DIM: usize, const HIDDEN: usize, const INPUTS: usize>
+    Network<T, INPUTS, INPUTS> for FeedForward<T, DIM, HIDDEN>
+{
+    fn evaluate(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> SVector<T, INPUTS> {
+        const { Self::check_width::<INPUTS>() };
+
+        let mut outputs = inputs;
+        for token in outputs.as_mut_slice().chunks_mut(DIM) {
+            let hidden = self
+                .hidden
+                .through(SVector::from_column_slice(token), activator);
+            token.copy_from_slice(self.output.through(hidden, &Linear).as_slice());
+        }
+
+        outputs
+    }
+}
+
+impl<T: RealField + Copy, const DIM: usize, const HIDDEN: usize, const INPUTS: usize>
+    NetworkInfo<INPUTS, INPUTS> for FeedForward<T, DIM, HIDDEN>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        let parameters =
+            self.hidden.info("hidden").parameters + self.output.info("output").parameters;
+
+        core::iter::once(LayerInfo {
+            parameters,
+            trainable_parameters: parameters,
+            ..LayerInfo::operation("feed_forward", "FeedForward", INPUTS)
+        })
+    }
+}
+
+/// A pre-norm transformer encoder block for a sequence of `SEQ` tokens, each with `DIM` values:
+/// ```text
+/// attended = inputs + attention(attention_norm(inputs))
+/// outputs = attended + feed_forward(feed_forward_norm(attended))
+/// ```
+/// Like [MultiHeadSelfAttention], the network has `SEQ * DIM` inputs and outputs. Blocks can be
+/// chained to make a deeper encoder.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransformerEncoderBlock<
+    T: RealField + Copy,
+    const SEQ: usize,
+    const DIM: usize,
+    const HEADS: usize,
+    const HIDDEN: usize,
+> {
+    /// Normalizes the inputs to the attention
+    pub attention_norm: LayerNorm<T, DIM>,
+    /// Mixes information between tokens
+    pub attention: MultiHeadSelfAttention<T, SEQ, DIM, HEADS>,
+    /// Normalizes the inputs to the feed forward network
+    pub feed_forward_norm: LayerNorm<T, DIM>,
+    /// Transforms each token
+    pub feed_forward: FeedForward<T, DIM, HIDDEN>,
+}
+
+impl<
+        T: RealField + Copy,
+        const SEQ: usize,
+        const DIM: usize,
+        const HEADS: usize,
+        const HIDDEN: usize,
+        const LEN: usize,
+    > Network<T, LEN, LEN> for TransformerEncoderBlock<T, SEQ, DIM, HEADS, HIDDEN>
+{
+    fn evaluate(&self, inputs: SVector<T, LEN>, activator: &impl Activator<T>) -> SVector<T, LEN> {
+        let attended = inputs
+            + self
+                .attention
+                .evaluate(self.attention_norm.evaluate(inputs, activator), activator);
+
+        attended
+            + self.feed_forward.evaluate(
+                self.feed_forward_norm.evaluate(attended, activator),
+                activator,
+            )
+    }
+}
+
+impl<
+        T: RealField + Copy,
+        const SEQ: usize,
+        const DIM: usize,
+        const HEADS: usize,
+        const HIDDEN: usize,
+        const LEN: usize,
+    > NetworkInfo<LEN, LEN> for TransformerEncoderBlock<T, SEQ, DIM, HEADS, HIDDEN>
+{
+    fn layer_info(&self) -> impl Iterator<Item = LayerInfo> {
+        // each part is described by a single entry, which is named after the part
+        let parts = NetworkInfo::<LEN, LEN>::layer_info(&self.attention_norm)
+            .chain(NetworkInfo::<LEN, LEN>::layer_info(&self.attention))
+            .chain(NetworkInfo::<LEN, LEN>::layer_info(&self.feed_forward_norm))
+            .chain(NetworkInfo::<LEN, LEN>::layer_info(&self.feed_forward));
+
+        [
+            "attention_norm",
+            "attention",
+            "feed_forward_norm",
+            "feed_forward",
+        ]
+        .into_iter()
+        .zip(parts)
+        .map(|(name, info)| LayerInfo { name, ..info })
+    }
+}
+
+#[cfg(feature = "backprop")]
+use crate::{LayerGradient, TrainableNetwork};
+#[cfg(feature = "random")]
+use {
+    crate::RandomisableNetwork,
+    rand::{distributions::Standard, prelude::Distribution, Rng},
+};
+
+#[cfg(feature = "backprop")]
+impl<T: RealField + Copy, const DIM: usize, const HIDDEN: usize, const INPUTS: usize>
+    TrainableNetwork<T, INPUTS, INPUTS> for FeedForward<T, DIM, HIDDEN>
+{
+    // the hidden values are cheap to recalculate, so only the inputs are kept
+    type LayerInputs = SVector<T, INPUTS>;
+
+    /// The gradients of the hidden and output layers
+    type Gradient = (LayerGradient<T, DIM, HIDDEN>, LayerGradient<T, HIDDEN, DIM>);
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, INPUTS>, Self::LayerInputs) {
+        (self.evaluate(inputs, activator), inputs)
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, INPUTS>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SVector<T, INPUTS>) {
+        use crate::ValueSet;
+
+        let mut gradient = Self::Gradient::default();
+        let mut input_gradients = SVector::<T, INPUTS>::zeros();
+
+        let tokens = layer_inputs
+            .as_slice()
+            .chunks(DIM)
+            .zip(output_loss_gradients.as_slice().chunks(DIM))
+            .zip(input_gradients.as_mut_slice().chunks_mut(DIM));
+
+        for ((token, loss_gradients), input_gradient) in tokens {
+            let token = SVector::from_column_slice(token);
+            let hidden = self.hidden.through(token, activator);
+
+            let (output_gradient, hidden_gradients) = self.output.backpropogate(
+                SVector::from_column_slice(loss_gradients),
+                hidden,
+                &Linear,
+            );
+            let (hidden_gradient, token_gradients) =
+                self.hidden
+                    .backpropogate(hidden_gradients, token, activator);
+
+            gradient =
+                gradient.binary_operation(&(hidden_gradient, output_gradient), |&a, &b| a + b);
+            input_gradient.copy_from_slice(token_gradients.as_slice());
+        }
+
+        (gradient, input_gradients)
+    }
+
+    fn apply_nudge(&mut self, nudge: Self::Gradient) {
+        let (hidden, output) = nudge;
+
+        self.hidden
+            .apply_shifts(hidden.weight_gradient, hidden.bias_gradient);
+        self.output
+            .apply_shifts(output.weight_gradient, output.bias_gradient);
+    }
+}
+
+#[cfg(feature = "random")]
+impl<T: RealField + Copy, const DIM: usize, const HIDDEN: usize> RandomisableNetwork<T>
+    for FeedForward<T, DIM, HIDDEN>
+where
+    Standard: Distribution<T>,
+{
+    fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            hidden: Layer::random(rng),
+            output: Layer::random(rng),
+        }
+    }
+}
+
+#[cfg(feature = "backprop")]
+impl<
+        T: RealField + Copy,
+        const SEQ: usize,
+        const DIM: usize,
+        const HEADS: usize,
+        const HIDDEN: usize,
+        const LEN: usize,
+    > TrainableNetwork<T, LEN, LEN> for TransformerEncoderBlock<T, SEQ, DIM, HEADS, HIDDEN>
+{
+    /// The inputs to the attention and feed forward halves
+    type LayerInputs = (SVector<T, LEN>, SVector<T, LEN>);
+
+    /// The gradients of the (attention_norm, attention) and (feed_forward_norm, feed_forward)
+    type Gradient = (
+        (
+            <LayerNorm<T, DIM> as TrainableNetwork<T, LEN, LEN>>::Gradient,
+            <MultiHeadSelfAttention<T, SEQ, DIM, HEADS> as TrainableNetwork<T, LEN, LEN>>::Gradient,
+        ),
+        (
+            <LayerNorm<T, DIM> as TrainableNetwork<T, LEN, LEN>>::Gradient,
+            <FeedForward<T, DIM, HIDDEN> as TrainableNetwork<T, LEN, LEN>>::Gradient,
+        ),
+    );
+
+    fn evaluate_training(
+        &self,
+        inputs: SVector<T, LEN>,
+        activator: &impl Activator<T>,
+    ) -> (SVector<T, LEN>, Self::LayerInputs) {
+        let attended = inputs
+            + self
+                .attention
+                .evaluate(self.attention_norm.evaluate(inputs, activator), activator);
+
+        let outputs = attended
+            + self.feed_forward.evaluate(
+                self.feed_forward_norm.evaluate(attended, activator),
+                activator,
+            );
+
+        (outputs, (inputs, attended))
+    }
+
+    fn get_gradient(
+        &self,
+        layer_inputs: &Self::LayerInputs,
+        output_loss_gradients: SVector<T, LEN>,
+        activator: &impl Activator<T>,
+    ) -> (Self::Gradient, SVector<T, LEN>) {
+        let (inputs, attended) = layer_inputs;
+
+        // the residual connections pass the loss gradient straight through, as well as through each half
+        let normalized = self.feed_forward_norm.evaluate(*attended, activator);
+        let (feed_forward_gradient, normalized_gradients) =
+            self.feed_forward
+                .get_gradient(&normalized, output_loss_gradients, activator);
+        let (feed_forward_norm_gradient, attended_gradients) =
+            self.feed_forward_norm
+                .get_gradient(attended, normalized_gradients, activator);
+        let attended_gradients = attended_gradients + output_loss_gradients;
+
+        let normalized = self.attention_norm.evaluate(*inputs, activator);
+        let (attention_gradient, normalized_gradients) =
+            self.attention
+                .get_gradient(&normalized, attended_gradients, activator);
+        let (attention_norm_gradient, input_gradients) =
+            self.attention_norm
+                .get_gradient(inputs, normalized_gradients, activator);
+
+        (
+            (
+                (attention_norm_gradient, attention_gradient),
+                (feed_forward_norm_gradient, feed_forward_gradient),
+            ),
+            input_gradients + attended_gradients,
+        )
+    }
+
+    fn apply_nudge(&mut self, nudge: Self::Gradient) {
+        let ((attention_norm, attention), (feed_forward_norm, feed_forward)) = nudge;
+
+        TrainableNetwork::<T, LEN, LEN>::apply_nudge(&mut self.attention_norm, attention_norm);
+        TrainableNetwork::<T, LEN, LEN>::apply_nudge(&mut self.attention, attention);
+        TrainableNetwork::<T, LEN, LEN>::apply_nudge(
+            &mut self.feed_forward_norm,
+            feed_forward_norm,
+        );
+        TrainableNetwork::<T, LEN, LEN>::apply_nudge(&mut self.feed_forward, feed_forward);
+    }
+}
+
+#[cfg(feature = "random")]
+impl<
+        T: RealField + Copy,
+        const SEQ: usize,
+        const DIM: usize,
+        const HEADS: usize,
+        const HIDDEN: usize,
+    > RandomisableNetwork<T> for TransformerEncoderBlock<T, SEQ, DIM, HEADS, HIDDEN>
+where
+    Standard: Distribution<T>,
+{
+    fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            attention_norm: LayerNorm::random(rng),
+            attention: MultiHeadSelfAttention::random(rng),
+            feed_forward_norm: LayerNorm::random(rng),
+            feed_forward: FeedForward::random(rng),
+        }
+    }
+}
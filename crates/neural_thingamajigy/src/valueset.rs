@@ -1,7 +1,8 @@
-extern crate std;
-
+use core::{iter::zip, marker::PhantomData};
 use nalgebra::{ComplexField, SMatrix};
-use std::iter::zip;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Represents anything which is a nested collection of a value
 pub trait ValueSet<T: Clone>: Sized {
@@ -19,6 +20,30 @@ pub trait ValueSet<T: Clone>: Sized {
 
     /// Creates a Self filled with v
     fn all(v: T) -> Self;
+
+    /// The number of parts(e.g. layers of a network's gradient) Self is made of
+    fn parts() -> usize {
+        1
+    }
+
+    /// Executes f for every entry, along with the index of the part containing it
+    fn indexed_inspection(&self, f: &mut impl FnMut(usize, &T)) {
+        self.unary_inspection(&mut |v| f(0, v));
+    }
+
+    /// Executes f for every entry, along with the index of the part containing it, returning the
+    /// transformed value
+    fn indexed_operation(&self, f: impl Fn(usize, &T) -> T) -> Self {
+        self.unary_operation(|v| f(0, v))
+    }
+
+    /// Returns self, keeping only the entries gradient has values for. Sparse gradients(e.g.
+    /// [crate::EmbeddingGradient]) override this so an optimiser's state can't update entries
+    /// the gradient didn't touch, everything else is dense so this returns a copy of self.
+    fn with_sparsity_of(&self, gradient: &Self) -> Self {
+        let _ = gradient;
+        self.unary_operation(|v| v.clone())
+    }
 }
 
 impl<T: ComplexField, const WIDTH: usize, const HEIGHT: usize> ValueSet<T>
@@ -70,6 +95,29 @@ impl<T: ComplexField, A: ValueSet<T>, B: ValueSet<T>> ValueSet<T> for (A, B) {
     fn all(v: T) -> Self {
         (A::all(v.clone()), B::all(v))
     }
+
+    fn parts() -> usize {
+        A::parts() + B::parts()
+    }
+
+    fn indexed_inspection(&self, f: &mut impl FnMut(usize, &T)) {
+        self.0.indexed_inspection(f);
+        self.1
+            .indexed_inspection(&mut |part, v| f(A::parts() + part, v));
+    }
+    fn indexed_operation(&self, f: impl Fn(usize, &T) -> T) -> Self {
+        (
+            self.0.indexed_operation(&f),
+            self.1.indexed_operation(|part, v| f(A::parts() + part, v)),
+        )
+    }
+
+    fn with_sparsity_of(&self, gradient: &Self) -> Self {
+        (
+            self.0.with_sparsity_of(&gradient.0),
+            self.1.with_sparsity_of(&gradient.1),
+        )
+    }
 }
 
 impl<T: ComplexField> ValueSet<T> for () {
@@ -84,6 +132,16 @@ impl<T: ComplexField> ValueSet<T> for () {
     fn binary_inspection(&self, _: &Self, _: &mut impl FnMut(&T, &T)) {}
 
     fn all(_: T) -> Self {}
+
+    fn parts() -> usize {
+        0
+    }
+
+    fn indexed_inspection(&self, _: &mut impl FnMut(usize, &T)) {}
+
+    fn indexed_operation(&self, _: impl Fn(usize, &T) -> T) -> Self {}
+
+    fn with_sparsity_of(&self, _: &Self) -> Self {}
 }
 
 /// Returns the sum and count of the ValueSets in v
@@ -103,8 +161,117 @@ pub fn mean<T: ComplexField + Copy, V: ValueSet<T> + Default>(v: &[V]) -> V {
     sum.unary_operation(|&x| x / count)
 }
 
+/// A running sum of ValueSets, using constant memory however many are added
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Accumulator<T, V> {
+    /// The sum of every added ValueSet
+    sum: V,
+    /// The rounding error lost from sum so far, when using compensated summation
+    compensation: Option<V>,
+    /// How many ValueSets have been added
+    count: usize,
+    /// The type of each value
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _marker: PhantomData<T>,
+}
+
+impl<T: ComplexField + Copy, V: ValueSet<T> + Default> Accumulator<T, V> {
+    /// Creates an empty accumulator which sums naively
+    pub fn new() -> Self {
+        Self {
+            sum: V::default(),
+            compensation: None,
+            count: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates an empty accumulator which uses Kahan summation, tracking the rounding error of
+    /// each addition. This is slower, but much more accurate when adding many small values to a
+    /// large sum, e.g. when accumulating lots of f32 gradients.
+    pub fn compensated() -> Self {
+        Self {
+            compensation: Some(V::default()),
+            ..Self::new()
+        }
+    }
+
+    /// Creates an empty accumulator using the same summation as self
+    pub fn empty_like(&self) -> Self {
+        match self.compensation {
+            Some(_) => Self::compensated(),
+            None => Self::new(),
+        }
+    }
+
+    /// Adds v to the sum
+    pub fn add(&mut self, v: &V) {
+        self.add_sum(v, 1);
+    }
+
+    /// Adds everything that has been added to other
+    pub fn merge(&mut self, other: &Self) {
+        match &other.compensation {
+            Some(compensation) => self.add_sum(
+                &other.sum.binary_operation(compensation, |&s, &c| s - c),
+                other.count,
+            ),
+            None => self.add_sum(&other.sum, other.count),
+        }
+    }
+
+    /// Adds sum, which is the sum of count ValueSets
+    pub fn add_sum(&mut self, sum: &V, count: usize) {
+        self.count += count;
+        match &mut self.compensation {
+            Some(compensation) => {
+                let corrected = sum.binary_operation(compensation, |&v, &c| v - c);
+                let new_sum = self.sum.binary_operation(&corrected, |&s, &v| s + v);
+                // (new - old) is what was actually added, minus what should have been gives the error
+                *compensation = new_sum
+                    .binary_operation(&self.sum, |&n, &s| n - s)
+                    .binary_operation(&corrected, |&added, &v| added - v);
+                self.sum = new_sum;
+            }
+            None => self.sum = self.sum.binary_operation(sum, |&s, &v| s + v),
+        }
+    }
+
+    /// How many ValueSets have been added
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The sum of every ValueSet added
+    pub fn sum(&self) -> V {
+        match &self.compensation {
+            Some(compensation) => self.sum.binary_operation(compensation, |&s, &c| s - c),
+            None => self.sum.unary_operation(|&s| s),
+        }
+    }
+
+    /// The mean(component-wise) of every ValueSet added
+    pub fn mean(&self) -> V {
+        let count: T = nalgebra::convert(self.count as f64);
+        self.sum().unary_operation(|&x| x / count)
+    }
+
+    /// Empties the accumulator, keeping its summation method
+    pub fn reset(&mut self) {
+        *self = self.empty_like();
+    }
+}
+
+impl<T: ComplexField + Copy, V: ValueSet<T> + Default> Default for Accumulator<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Tests for ValueSet on matrices
 mod test {
+    #[cfg(feature = "std-train")]
     #[test]
     fn test_gradient_impl() {
         use nalgebra::SMatrix;
@@ -115,4 +282,30 @@ mod test {
 
         assert_eq!(a + b, a.binary_operation(&b, |p, q| p + q))
     }
+
+    #[test]
+    fn test_compensated_accumulator() {
+        use crate::valueset::Accumulator;
+        use nalgebra::Vector1;
+
+        let mut naive = Accumulator::new();
+        let mut compensated = Accumulator::compensated();
+
+        naive.add(&Vector1::new(1f32));
+        compensated.add(&Vector1::new(1f32));
+        for _ in 0..1000 {
+            // too small to change 1f32 when added on its own
+            naive.add(&Vector1::new(1e-8f32));
+            compensated.add(&Vector1::new(1e-8f32));
+        }
+
+        assert_eq!(naive.sum().x, 1f32);
+        assert!((compensated.sum().x - 1.00001f32).abs() < 1e-7);
+        assert_eq!(compensated.count(), 1001);
+
+        let mut merged = Accumulator::new();
+        merged.merge(&compensated);
+        merged.merge(&compensated);
+        assert!((merged.mean().x - 1.00001f32 / 1001f32).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,90 @@
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators,
+    adversarial::{robustness, train_adversarial, Attack, Budget},
+    loss::squared_error,
+    optimiser::AdamOptimiser,
+    train, Network, RandomisableNetwork,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+network!(pub MyNetwork, f64, 2, 4, 1);
+
+/// Adversarial training should match normal training when the adversarial examples are ignored,
+/// and should make the network more robust when they aren't
+#[test]
+fn adversarial_training_test() {
+    let activator = activators::Sigmoid;
+    let data = [
+        (Vector2::new(0., 0.), Vector1::new(0.)),
+        (Vector2::new(0., 1.), Vector1::new(1.)),
+        (Vector2::new(1., 0.), Vector1::new(1.)),
+        (Vector2::new(1., 1.), Vector1::new(0.)),
+    ];
+    let attack = Attack::Pgd {
+        budget: Budget::LInfinity(0.1),
+        step_size: 0.03,
+        steps: 5,
+    };
+
+    let mut clean = MyNetwork::random(&mut ChaCha8Rng::seed_from_u64(2));
+    let mut ignored = MyNetwork::random(&mut ChaCha8Rng::seed_from_u64(2));
+    let mut adversarial = MyNetwork::random(&mut ChaCha8Rng::seed_from_u64(2));
+    let (mut clean_opt, mut ignored_opt, mut adversarial_opt) = (
+        AdamOptimiser::default(),
+        AdamOptimiser::default(),
+        AdamOptimiser::default(),
+    );
+
+    for _ in 0..2000 {
+        let clean_loss = train(
+            data.iter(),
+            &mut clean,
+            &activator,
+            &squared_error,
+            &mut clean_opt,
+        )
+        .unwrap();
+        let ignored_loss = train_adversarial(
+            data.iter(),
+            &mut ignored,
+            &activator,
+            &squared_error,
+            &mut ignored_opt,
+            &attack,
+            0.,
+        )
+        .unwrap();
+        assert!((clean_loss - ignored_loss).abs() < 1e-12);
+
+        train_adversarial(
+            data.iter(),
+            &mut adversarial,
+            &activator,
+            &squared_error,
+            &mut adversarial_opt,
+            &attack,
+            0.5,
+        )
+        .unwrap();
+    }
+
+    for (x, _) in &data {
+        assert!((clean.evaluate(*x, &activator) - ignored.evaluate(*x, &activator)).norm() < 1e-9);
+    }
+
+    let clean_report =
+        robustness(data.iter(), &clean, &activator, &squared_error, &attack).unwrap();
+    let adversarial_report = robustness(
+        data.iter(),
+        &adversarial,
+        &activator,
+        &squared_error,
+        &attack,
+    )
+    .unwrap();
+    assert!(clean_report.adversarial_loss >= clean_report.clean_loss);
+    assert!(adversarial_report.adversarial_loss < clean_report.adversarial_loss);
+}
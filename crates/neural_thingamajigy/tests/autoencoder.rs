@@ -0,0 +1,106 @@
+use nalgebra::{SVector, Vector4};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators,
+    autoencoder::{train_autoencoder, train_variational, AutoEncoder, VariationalAutoEncoder},
+    loss::squared_error,
+    optimiser::AdamOptimiser,
+    RandomisableNetwork,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+network!(pub Encoder, f64, 4, 6, 2);
+network!(pub Decoder, f64, 2, 6, 4);
+network!(pub VariationalEncoder, f64, 4, 6, 4);
+
+/// Samples lying on a line, which a 2 wide bottleneck can represent
+fn line(rng: &mut impl Rng) -> Vec<SVector<f64, 4>> {
+    (0..64)
+        .map(|_| {
+            let t = rng.gen_range(0.0..1.0);
+            Vector4::new(t, 1. - t, t * 0.5, 0.25)
+        })
+        .collect()
+}
+
+/// Autoencoders should learn to reconstruct their training data better than an outlier
+#[test]
+fn autoencoder_test() {
+    let activator = activators::Sigmoid;
+    let mut rng = ChaCha8Rng::seed_from_u64(11);
+    let data = line(&mut rng);
+
+    let mut autoencoder = AutoEncoder::new(Encoder::random(&mut rng), Decoder::random(&mut rng));
+    let mut opt = AdamOptimiser::new(0.02, 0.9, 0.999);
+
+    let first_loss = train_autoencoder(
+        data.iter(),
+        &mut autoencoder,
+        &activator,
+        &squared_error,
+        &mut opt,
+    )
+    .unwrap();
+    let mut last_loss = first_loss;
+    for _ in 0..200 {
+        last_loss = train_autoencoder(
+            data.iter(),
+            &mut autoencoder,
+            &activator,
+            &squared_error,
+            &mut opt,
+        )
+        .unwrap();
+    }
+    assert!(last_loss < first_loss);
+
+    let latent = autoencoder.encode(data[0], &activator);
+    assert_eq!(
+        autoencoder.decode(latent, &activator),
+        autoencoder.reconstruct(data[0], &activator)
+    );
+
+    let outlier = Vector4::new(1., 1., 1., 1.);
+    assert!(
+        autoencoder.reconstruction_error(outlier, &activator, &squared_error)
+            > autoencoder.reconstruction_error(data[0], &activator, &squared_error)
+    );
+
+    let mut vae = VariationalAutoEncoder::new(
+        VariationalEncoder::random(&mut rng),
+        Decoder::random(&mut rng),
+    );
+    vae.kl_weight = 0.1;
+    let mut opt = AdamOptimiser::new(0.02, 0.9, 0.999);
+
+    let mut losses = Vec::new();
+    for _ in 0..200 {
+        losses.push(
+            train_variational(
+                data.iter(),
+                &mut vae,
+                &activator,
+                &squared_error,
+                &mut opt,
+                &mut rng,
+            )
+            .unwrap(),
+        );
+    }
+    // sampling makes the loss noisy, so compare the average of the first and last epochs
+    let first: f64 = losses[..20].iter().sum();
+    let last: f64 = losses[180..].iter().sum();
+    assert!(last < first);
+
+    let (mean, log_variance) = vae.encode(data[0], &activator);
+    assert_eq!(
+        vae.reconstruct(data[0], &activator),
+        vae.decode(mean, &activator)
+    );
+    assert!(log_variance.iter().all(|v| v.is_finite()));
+    assert!(vae
+        .generate(&mut rng, &activator)
+        .iter()
+        .all(|v| v.is_finite()));
+}
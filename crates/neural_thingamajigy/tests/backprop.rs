@@ -0,0 +1,109 @@
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    accumulate_sample, activators, apply_gradient, get_loss, loss::squared_error,
+    optimiser::AdamOptimiser, train, train_step, RandomisableNetwork, TrainError, ValueSet,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+network!(pub MyNetwork, f64, 2, 3, 1);
+
+/// Training into a caller-provided gradient should match the allocating training loop
+#[test]
+fn train_step_test() {
+    let activator = activators::Sigmoid;
+    let mut network = MyNetwork::random(&mut ChaCha8Rng::seed_from_u64(1));
+    let mut reference = MyNetwork::random(&mut ChaCha8Rng::seed_from_u64(1));
+    let (mut opt, mut reference_opt) = (AdamOptimiser::default(), AdamOptimiser::default());
+    let mut gradient = MyNetworkGradient::default();
+
+    let data = [
+        (Vector2::new(0., 0.), Vector1::new(0.)),
+        (Vector2::new(0., 1.), Vector1::new(1.)),
+        (Vector2::new(1., 0.), Vector1::new(1.)),
+        (Vector2::new(1., 1.), Vector1::new(0.)),
+    ];
+
+    let first = get_loss(data.iter(), &network, &activator, &squared_error).unwrap();
+    for _ in 0..100 {
+        let loss = train_step(
+            data.iter(),
+            &mut network,
+            &activator,
+            &squared_error,
+            &mut opt,
+            &mut gradient,
+        )
+        .unwrap();
+        let reference_loss = train(
+            data.iter(),
+            &mut reference,
+            &activator,
+            &squared_error,
+            &mut reference_opt,
+        )
+        .unwrap();
+        assert!((loss - reference_loss).abs() < 1e-12);
+    }
+    assert!(get_loss(data.iter(), &network, &activator, &squared_error).unwrap() < first);
+
+    assert_eq!(
+        train_step(
+            [].iter(),
+            &mut network,
+            &activator,
+            &squared_error,
+            &mut opt,
+            &mut gradient
+        ),
+        Err(TrainError::EmptyDataset)
+    );
+}
+
+/// Errors from the allocation free functions should say which sample caused them, and never
+/// leave a stale gradient behind
+#[test]
+fn accumulate_sample_test() {
+    let activator = activators::Sigmoid;
+    let mut network = MyNetwork::random(&mut ChaCha8Rng::seed_from_u64(2));
+    let mut opt = AdamOptimiser::default();
+    let mut gradient = MyNetworkGradient::default();
+
+    let data = [
+        (Vector2::new(0., 1.), Vector1::new(1.)),
+        (Vector2::new(1., 0.), Vector1::new(f64::NAN)),
+    ];
+    let (input, expected) = &data[0];
+    accumulate_sample(
+        *input,
+        expected,
+        &network,
+        &activator,
+        &squared_error,
+        &mut gradient,
+        0,
+    )
+    .unwrap();
+    let (input, expected) = &data[1];
+    assert_eq!(
+        accumulate_sample(
+            *input,
+            expected,
+            &network,
+            &activator,
+            &squared_error,
+            &mut gradient,
+            1
+        ),
+        Err(TrainError::NonFiniteLoss { sample: 1 })
+    );
+
+    assert_eq!(
+        apply_gradient(&mut network, &mut opt, &mut gradient, 0),
+        Err(TrainError::EmptyDataset)
+    );
+    let mut nonzero = false;
+    gradient.unary_inspection(&mut |&g| nonzero |= g != 0.);
+    assert!(!nonzero);
+}
@@ -0,0 +1,115 @@
+use nalgebra::{DMatrix, SMatrix, SVector};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators::{self, Activator},
+    loss::squared_error,
+    operations::{Softmax, StandardScaler},
+    optimiser::AdamOptimiser,
+    train, train_batched, ChainableNetwork, Layer, Network, RandomisableNetwork,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+network!(pub MyNetwork, f64, 3, 8, 8, 4);
+network!(pub Head, f64, 4, 2);
+
+/// Evaluating a batch should give the same outputs as evaluating each column
+#[test]
+fn batch_test() {
+    let activator = activators::Elu;
+    let mut rng = ChaCha8Rng::seed_from_u64(8);
+
+    let inputs = SMatrix::<f64, 3, 10>::from_fn(|_, _| rng.gen_range(-1.0..1.0));
+    let columns: Vec<_> = inputs.column_iter().map(|c| c.into_owned()).collect();
+    let scaler = StandardScaler::fit(&columns);
+    let network = scaler.chain(MyNetwork::random(&mut rng)).chain(Softmax);
+
+    let outputs = network.evaluate_batch(inputs, &activator);
+    for (column, output) in columns.iter().zip(outputs.column_iter()) {
+        assert!((network.evaluate(*column, &activator) - output).norm() < 1e-12);
+    }
+
+    // more columns than fit in one batch, and a partial last batch
+    let inputs = DMatrix::<f64>::from_fn(3, 150, |_, _| rng.gen_range(-1.0..1.0));
+    let outputs = network.evaluate_dynamic_batch(&inputs, &activator);
+    assert_eq!(outputs.shape(), (4, 150));
+    for (column, output) in inputs.column_iter().zip(outputs.column_iter()) {
+        let expected = network.evaluate(column.fixed_rows::<3>(0).into_owned(), &activator);
+        assert!((expected - output).norm() < 1e-12);
+    }
+}
+
+/// A layer's gradients for one sample should match its batch gradients, and the diagonal
+/// activation gradient matrix
+#[test]
+fn layer_gradient_test() {
+    let activator = activators::Elu;
+    let mut rng = ChaCha8Rng::seed_from_u64(11);
+
+    let layer = Layer::<f64, 3, 4>::random(&mut rng);
+    let inputs = SVector::<f64, 3>::from_fn(|_, _| rng.gen_range(-1.0..1.0));
+    let loss_gradients = SVector::<f64, 4>::from_fn(|_, _| rng.gen_range(-1.0..1.0));
+
+    let (gradient, input_gradients) = layer.backpropogate(loss_gradients, inputs, &activator);
+    let (batch_gradient, batch_input_gradients) =
+        layer.backpropogate_batch::<1>(loss_gradients, inputs, &activator);
+    assert!((gradient.weight_gradient - batch_gradient.weight_gradient).norm() < 1e-12);
+    assert_eq!(gradient.bias_gradient, batch_gradient.bias_gradient);
+    assert!((input_gradients - batch_input_gradients).norm() < 1e-12);
+    assert_eq!(
+        layer.backpropogate_inputs(loss_gradients, inputs, &activator),
+        input_gradients
+    );
+
+    let matrix = activator.activation_gradient_matrix(layer.weights() * inputs) * layer.weights();
+    assert!((layer.gradient(inputs, &activator) - matrix).norm() < 1e-12);
+}
+
+/// Batched training should match training one sample at a time, up to rounding
+#[test]
+fn batch_training_test() {
+    let activator = activators::Elu;
+    let mut rng = ChaCha8Rng::seed_from_u64(9);
+
+    // 10 samples don't fill the last batch of 4
+    let data: Vec<_> = (0..10)
+        .map(|_| {
+            (
+                SVector::<f64, 3>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+                SVector::<f64, 2>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+            )
+        })
+        .collect();
+
+    let network = || {
+        let mut rng = ChaCha8Rng::seed_from_u64(10);
+        MyNetwork::random(&mut rng).chain(Head::random(&mut rng))
+    };
+    let (mut single, mut batched) = (network(), network());
+
+    let (mut single_opt, mut batched_opt) = (AdamOptimiser::default(), AdamOptimiser::default());
+    for _ in 0..20 {
+        let single_loss = train(
+            data.iter(),
+            &mut single,
+            &activator,
+            &squared_error,
+            &mut single_opt,
+        )
+        .unwrap();
+        let batched_loss = train_batched::<4, _, _, 3, 2>(
+            &data,
+            &mut batched,
+            &activator,
+            &squared_error,
+            &mut batched_opt,
+        )
+        .unwrap();
+        assert!((single_loss - batched_loss).abs() < 1e-9);
+    }
+
+    for (x, _) in &data {
+        let difference = single.evaluate(*x, &activator) - batched.evaluate(*x, &activator);
+        assert!(difference.norm() < 1e-9);
+    }
+}
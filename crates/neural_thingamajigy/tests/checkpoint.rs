@@ -0,0 +1,73 @@
+#![cfg(all(feature = "train", feature = "serde"))]
+
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators, checkpoint::Checkpoint, loss::squared_error, optimiser::AdamOptimiser, train,
+    Network, RandomisableNetwork,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+network!(pub MyNetwork, f64, 2, 4, 1);
+
+/// Training resumed from a checkpoint should continue exactly as if it was never interrupted
+#[test]
+fn resume_test() {
+    let activator = activators::Relu {
+        leaky_gradient: 0.01,
+    };
+    let data = [
+        (Vector2::new(0., 0.), Vector1::new(0.)),
+        (Vector2::new(1., 0.), Vector1::new(1.)),
+        (Vector2::new(0., 1.), Vector1::new(1.)),
+        (Vector2::new(1., 1.), Vector1::new(0.)),
+    ];
+
+    let mut rng = ChaCha8Rng::seed_from_u64(7);
+    let mut network = MyNetwork::random(&mut rng);
+    let mut opt = AdamOptimiser::default();
+
+    for _ in 0..3 {
+        train(
+            data.iter(),
+            &mut network,
+            &activator,
+            &squared_error,
+            &mut opt,
+        )
+        .unwrap();
+    }
+
+    let saved = serde_json::to_string(&Checkpoint::new(&network, &opt, 3, &rng)).unwrap();
+    let mut resumed: Checkpoint<MyNetwork, AdamOptimiser<f64, _>, ChaCha8Rng> =
+        serde_json::from_str(&saved).unwrap();
+    assert_eq!(resumed.epoch, 3);
+
+    for _ in 0..3 {
+        train(
+            data.iter(),
+            &mut network,
+            &activator,
+            &squared_error,
+            &mut opt,
+        )
+        .unwrap();
+        train(
+            data.iter(),
+            &mut resumed.network,
+            &activator,
+            &squared_error,
+            &mut resumed.optimiser,
+        )
+        .unwrap();
+    }
+
+    for (x, _) in &data {
+        assert_eq!(
+            network.evaluate(*x, &activator),
+            resumed.network.evaluate(*x, &activator)
+        );
+    }
+    assert_eq!(rng.gen::<u64>(), resumed.rng.gen::<u64>());
+}
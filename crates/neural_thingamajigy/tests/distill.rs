@@ -0,0 +1,53 @@
+use nalgebra::{Vector2, Vector3};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators, distill::Distiller, loss::squared_error, optimiser::AdamOptimiser, Network,
+    RandomisableNetwork,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+network!(pub Teacher, f64, 2, 16, 16, 3);
+network!(pub Student, f64, 2, 4, 3);
+
+/// A student trained only on the teacher's soft targets should learn to imitate it
+#[test]
+fn distillation_test() {
+    let activator = activators::Sigmoid;
+    let mut rng = ChaCha8Rng::seed_from_u64(6);
+
+    let distiller = Distiller::new(Teacher::random(&mut rng), activators::Sigmoid, 2., 1.);
+    let mut student = Student::random(&mut rng);
+    let mut opt = AdamOptimiser::new(0.01, 0.9, 0.999);
+
+    // the hard labels are ignored, so they can be anything
+    let data: Vec<_> = (0..32)
+        .map(|_| {
+            (
+                Vector2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)),
+                Vector3::zeros(),
+            )
+        })
+        .collect();
+
+    let train = |student: &mut Student, opt: &mut AdamOptimiser<f64, _>| {
+        distiller
+            .train(data.iter(), student, &activator, &squared_error, opt)
+            .unwrap()
+    };
+
+    let before = train(&mut student, &mut opt);
+    let mut after = before;
+    for _ in 0..500 {
+        after = train(&mut student, &mut opt);
+    }
+    assert!(after < before / 4.);
+
+    // the student's outputs are logits, so imitating the teacher means the same softmax
+    let (x, _) = data[0];
+    let teacher = distiller.soft_targets(x);
+    let logits: Vector3<f64> = student.evaluate(x, &activator);
+    let student =
+        neural_thingamajigy::operations::Softmax.evaluate(logits / 2., &activators::Linear);
+    assert!((teacher - student).norm() < 0.1);
+}
@@ -0,0 +1,48 @@
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators, get_loss, loss::squared_error, optimiser::AdamOptimiser, train, ChainableNetwork,
+    Embedding, RandomisableNetwork,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+network!(pub Head, f64, 6, 4, 1);
+
+/// An embedding chained in front of dense layers should train end to end
+#[test]
+fn embedding_chain_test() {
+    let activator = activators::Sigmoid;
+    let mut rng = ChaCha8Rng::seed_from_u64(5);
+
+    // 2 tokens from a vocabulary of 5, each embedded as 3 values
+    let mut network = Embedding::<f64, 5, 3>::random(&mut rng).chain(Head::random(&mut rng));
+
+    // 1 if both tokens are the same
+    let data: Vec<_> = (0..5)
+        .flat_map(|a| (0..5).map(move |b| (a, b)))
+        .map(|(a, b)| {
+            (
+                Vector2::new(a as f64, b as f64),
+                Vector1::new(if a == b { 1. } else { 0. }),
+            )
+        })
+        .collect();
+
+    let mut opt = AdamOptimiser::new(0.05, 0.9, 0.999);
+    let first_loss = get_loss(data.iter(), &network, &activator, &squared_error).unwrap();
+
+    for _ in 0..50 {
+        train(
+            data.iter(),
+            &mut network,
+            &activator,
+            &squared_error,
+            &mut opt,
+        )
+        .unwrap();
+    }
+
+    let last_loss = get_loss(data.iter(), &network, &activator, &squared_error).unwrap();
+    assert!(last_loss < first_loss);
+}
@@ -0,0 +1,132 @@
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators, get_loss,
+    loss::{squared_error, ReducedLoss, Reduction},
+    optimiser::AdamOptimiser,
+    train, train_weighted, Network, RandomisableNetwork, TrainError,
+};
+use rand::rngs::OsRng;
+
+network!(pub MyNetwork, f64, 2, 3, 1);
+
+/// Bad data should be reported, rather than corrupting the network
+#[test]
+fn error_test() {
+    let activator = activators::Sigmoid;
+    let mut network = MyNetwork::random(&mut OsRng);
+    let mut opt = AdamOptimiser::default();
+
+    assert_eq!(
+        train(
+            [].iter(),
+            &mut network,
+            &activator,
+            &squared_error,
+            &mut opt
+        ),
+        Err(TrainError::EmptyDataset)
+    );
+    assert_eq!(
+        get_loss([].iter(), &network, &activator, &squared_error),
+        Err(TrainError::EmptyDataset)
+    );
+
+    // more samples than fit in a u8
+    let mut data: Vec<_> = (0..300)
+        .map(|i| (Vector2::new(i as f64 / 300., 1.), Vector1::new(0.5)))
+        .collect();
+    let loss = train(
+        data.iter(),
+        &mut network,
+        &activator,
+        &squared_error,
+        &mut opt,
+    )
+    .unwrap();
+    assert!(loss.is_finite());
+
+    data[270].1.x = f64::NAN;
+    let before = network.evaluate(data[0].0, &activator);
+    assert_eq!(
+        train(
+            data.iter(),
+            &mut network,
+            &activator,
+            &squared_error,
+            &mut opt
+        ),
+        Err(TrainError::NonFiniteLoss { sample: 270 })
+    );
+    assert_eq!(network.evaluate(data[0].0, &activator), before);
+}
+
+/// Weights must match the data, and the mean is taken over the weights rather than the samples
+#[test]
+fn weighted_test() {
+    let activator = activators::Sigmoid;
+    let mut network = MyNetwork::random(&mut OsRng);
+    let mut opt = AdamOptimiser::default();
+    let data: Vec<_> = (0..300)
+        .map(|i| (Vector2::new(i as f64 / 300., 1.), Vector1::new(0.5)))
+        .collect();
+
+    assert_eq!(
+        train_weighted(
+            &data,
+            &[1.; 299],
+            &mut network,
+            &activator,
+            &squared_error,
+            Reduction::Mean,
+            &mut opt
+        ),
+        Err(TrainError::ShapeMismatch {
+            expected: 300,
+            found: 299
+        })
+    );
+    let weights: Vec<_> = (0..300).map(|i| (i % 2) as f64).collect();
+    let Ok(ReducedLoss::PerSample(losses)) = train_weighted(
+        &data,
+        &weights,
+        &mut network,
+        &activator,
+        &squared_error,
+        Reduction::None,
+        &mut opt,
+    ) else {
+        panic!("expected a loss per sample")
+    };
+    assert_eq!(losses.len(), 300);
+    assert_eq!(losses[0], 0.); // weighted by 0
+    assert!(losses[1] > 0.);
+
+    // every sample weighted the same is the unweighted mean, whatever the weight
+    let loss = get_loss(data.iter(), &network, &activator, &squared_error).unwrap();
+    let Ok(ReducedLoss::Scalar(weighted)) = train_weighted(
+        &data,
+        &[3.; 300],
+        &mut network,
+        &activator,
+        &squared_error,
+        Reduction::Mean,
+        &mut opt,
+    ) else {
+        panic!("expected a single loss")
+    };
+    assert!((weighted - loss).abs() < 1e-12);
+
+    assert_eq!(
+        train_weighted(
+            &data,
+            &[0.; 300],
+            &mut network,
+            &activator,
+            &squared_error,
+            Reduction::Mean,
+            &mut opt
+        ),
+        Err(TrainError::EmptyDataset)
+    );
+}
@@ -0,0 +1,99 @@
+use nalgebra::SVector;
+use network_macro::network;
+use neural_thingamajigy::{
+    activators,
+    loss::squared_error,
+    optimiser::{AdamOptimiser, LearningRateMultipliers},
+    train, train_batched, ChainableNetwork, Frozen, Network, RandomisableNetwork, TrainableNetwork,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+network!(pub Extractor, f64, 3, 6, features: 4);
+network!(pub Head, f64, 4, 2);
+
+/// Frozen networks should pass gradients through without being trained
+#[test]
+fn frozen_test() {
+    let activator = activators::Elu;
+    let mut rng = ChaCha8Rng::seed_from_u64(11);
+    let data: Vec<_> = (0..16)
+        .map(|_| {
+            (
+                SVector::<f64, 3>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+                SVector::<f64, 2>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+            )
+        })
+        .collect();
+    let (input, _) = data[0];
+
+    let mut extractor = Extractor::random(&mut rng);
+    let mut head = Head::random(&mut rng);
+    let features = extractor.evaluate(input, &activator);
+    let predicted = head.evaluate(features, &activator);
+
+    // the input gradient is the same as if it wasn't frozen
+    let unfrozen = (&mut extractor).chain(&mut head);
+    let (_, layer_inputs) = unfrozen.evaluate_training(input, &activator);
+    let (_, expected) = unfrozen.get_gradient(&layer_inputs, SVector::repeat(1.), &activator);
+    let frozen = Frozen(&mut extractor).chain(&mut head);
+    let (((), _), frozen_gradient) =
+        frozen.get_gradient(&layer_inputs, SVector::repeat(1.), &activator);
+    assert!((frozen_gradient - expected).norm() < 1e-12);
+    let input_gradient = frozen.get_input_gradient(&layer_inputs, SVector::repeat(1.), &activator);
+    assert!((input_gradient - expected).norm() < 1e-12);
+
+    let mut fine_tuned = Frozen(&mut extractor).chain(&mut head);
+    let mut opt = AdamOptimiser::new(0.01, 0.9, 0.999);
+    for _ in 0..10 {
+        train(
+            data.iter(),
+            &mut fine_tuned,
+            &activator,
+            &squared_error,
+            &mut opt,
+        )
+        .unwrap();
+    }
+    assert_eq!(extractor.evaluate(input, &activator), features);
+    assert_ne!(head.evaluate(features, &activator), predicted);
+
+    // batched training skips the frozen network too
+    let mut fine_tuned = Frozen(extractor).chain(head);
+    let mut opt = AdamOptimiser::new(0.01, 0.9, 0.999);
+    train_batched::<4, _, _, 3, 2>(&data, &mut fine_tuned, &activator, &squared_error, &mut opt)
+        .unwrap();
+    assert_eq!(fine_tuned.first.0.evaluate(input, &activator), features);
+}
+
+/// A learning rate multiplier of 0 should freeze a single layer
+#[test]
+fn learning_rate_multipliers_test() {
+    let activator = activators::Elu;
+    let mut rng = ChaCha8Rng::seed_from_u64(12);
+    let data: Vec<_> = (0..16)
+        .map(|_| {
+            (
+                SVector::<f64, 3>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+                SVector::<f64, 4>::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+            )
+        })
+        .collect();
+    let (input, _) = data[0];
+
+    let mut network = Extractor::random(&mut rng);
+    let first = network.layer0().through(input, &activator);
+    let second = network.features().through(first, &activator);
+
+    let mut opt = LearningRateMultipliers::new(AdamOptimiser::new(0.01, 0.9, 0.999), [0., 1.]);
+    train(
+        data.iter(),
+        &mut network,
+        &activator,
+        &squared_error,
+        &mut opt,
+    )
+    .unwrap();
+    assert_eq!(network.layer0().through(input, &activator), first);
+    assert_ne!(network.features().through(first, &activator), second);
+}
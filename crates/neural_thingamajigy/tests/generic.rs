@@ -0,0 +1,63 @@
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators, get_loss, loss::squared_error, optimiser::AdamOptimiser, train, Network,
+    RandomisableNetwork, TrainableNetwork,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+network!(pub Generic<T>, 2, 4 * 2, 1);
+
+/// The loss of a network of any precision on xor
+fn xor_loss<T: nalgebra::RealField + Copy + std::iter::Sum>(network: &impl Network<T, 2, 1>) -> T {
+    let data: Vec<_> = [(0., 0., 0.), (0., 1., 1.), (1., 0., 1.), (1., 1., 0.)]
+        .iter()
+        .map(|&(a, b, y): &(f64, f64, f64)| {
+            (
+                Vector2::new(nalgebra::convert(a), nalgebra::convert(b)),
+                Vector1::new(nalgebra::convert(y)),
+            )
+        })
+        .collect();
+
+    get_loss(data.iter(), network, &activators::Sigmoid, &squared_error).unwrap()
+}
+
+/// The same architecture can be trained in f64 and deployed in f32
+#[test]
+fn generic_test() {
+    let mut network = Generic::<f64>::random(&mut ChaCha8Rng::seed_from_u64(1));
+    let mut opt = AdamOptimiser::new(0.05, 0.9, 0.999);
+    let data = [
+        (Vector2::new(0., 0.), Vector1::new(0.)),
+        (Vector2::new(0., 1.), Vector1::new(1.)),
+        (Vector2::new(1., 0.), Vector1::new(1.)),
+        (Vector2::new(1., 1.), Vector1::new(0.)),
+    ];
+
+    let before = xor_loss(&network);
+    for _ in 0..200 {
+        train(
+            data.iter(),
+            &mut network,
+            &activators::Sigmoid,
+            &squared_error,
+            &mut opt,
+        )
+        .unwrap();
+    }
+    let after = xor_loss(&network);
+    assert!(after < before);
+
+    let deployed: Generic<f32> = network.cast();
+    assert!((xor_loss(&deployed) as f64 - after).abs() < 1e-5);
+    // casting back loses only the f32 rounding
+    assert!((xor_loss(&deployed.cast::<f64>()) - after).abs() < 1e-5);
+
+    // the generated gradient is generic too
+    let (_, layer_inputs) = deployed.evaluate_training(Vector2::new(1., 0.), &activators::Sigmoid);
+    let (_, input_gradient) =
+        deployed.get_gradient(&layer_inputs, Vector1::new(1f32), &activators::Sigmoid);
+    assert!(input_gradient.iter().all(|g| g.is_finite()));
+}
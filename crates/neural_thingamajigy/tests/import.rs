@@ -0,0 +1,167 @@
+use nalgebra::{Matrix2x4, Vector2, Vector3, Vector4};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators::Linear,
+    data::DataError,
+    import::{read_safetensors, state_dict_layers, Activation, DynNetwork, ImportableNetwork},
+    Network, NetworkInfo,
+};
+
+network!(pub Mlp<T>, 3, hidden: 4, out: 2);
+
+/// The file `safetensors.torch.save_file(model.state_dict(), ...)` writes for
+/// `model = nn.Sequential(nn.Linear(3, 4), nn.ReLU(), nn.Linear(4, 2))`
+fn safetensors() -> Vec<u8> {
+    let tensors: [(&str, &[usize], &[f32]); 4] = [
+        ("0.bias", &[4], &[0.5, -0.25, 1., -0.5]),
+        (
+            "0.weight",
+            &[4, 3],
+            &[0.5, -0.25, 1., -1., 0.5, 0.25, 0.25, 0.75, -0.5, 1., 1., 1.],
+        ),
+        ("2.bias", &[2], &[0.25, -0.5]),
+        (
+            "2.weight",
+            &[2, 4],
+            &[1., -0.5, 0.25, 2., -0.75, 1., 0.5, -1.],
+        ),
+    ];
+
+    let (mut entries, mut data) = (Vec::new(), Vec::new());
+    for (name, shape, values) in tensors {
+        let start = data.len();
+        data.extend(values.iter().flat_map(|v| v.to_le_bytes()));
+        entries.push(format!(
+            r#""{}":{{"dtype":"F32","shape":{:?},"data_offsets":[{},{}]}}"#,
+            name,
+            shape,
+            start,
+            data.len()
+        ));
+    }
+
+    // the header is padded with spaces to a multiple of 8 bytes
+    let mut header = format!("{{{}}}", entries.join(","));
+    while header.len() % 8 != 0 {
+        header.push(' ');
+    }
+
+    let mut file = (header.len() as u64).to_le_bytes().to_vec();
+    file.extend_from_slice(header.as_bytes());
+    file.extend(data);
+    file
+}
+
+/// An MLP exported from PyTorch should give the same outputs as it does in PyTorch
+#[test]
+fn pytorch_test() {
+    let state_dict = read_safetensors(safetensors().as_slice()).unwrap();
+    // state-dicts don't hold activations, so the ReLU between the layers is added
+    let mut layers = state_dict_layers(&state_dict, &["0", "2"]).unwrap();
+    layers[0].activation = Some(Activation::Relu);
+    let network = DynNetwork::<f32, 3, 2>::from_dense_layers(&layers).unwrap();
+
+    // `model(x)` in PyTorch, which is exact as every value is a multiple of 1/32
+    for (x, y) in [
+        (Vector3::new(1., -2., 0.5), Vector2::new(2.25, -2.)),
+        (Vector3::new(0.5, 1., -1.), Vector2::new(0.84375, 0.6875)),
+        (Vector3::new(-1., 0., 2.), Vector2::new(2.625, -1.25)),
+    ] {
+        assert_eq!(network.evaluate(x, &Linear), y);
+    }
+    assert_eq!(network.parameter_count(), (3 * 4 + 4) + (4 * 2 + 2));
+
+    // layers add their bias after activating, so a network! can't hold the ReLU
+    assert!(matches!(
+        Mlp::<f32>::from_dense_layers(&layers),
+        Err(DataError::Unsupported(_))
+    ));
+
+    // but without it, both give the same outputs
+    let mlp = Mlp::<f32>::from_state_dict(&state_dict, &["0", "2"]).unwrap();
+    assert_eq!(
+        mlp.out().weights(),
+        &Matrix2x4::new(1., -0.5, 0.25, 2., -0.75, 1., 0.5, -1.)
+    );
+    assert_eq!(mlp.hidden().bias(), &Vector4::new(0.5, -0.25, 1., -0.5));
+    let linear = DynNetwork::<f32, 3, 2>::from_state_dict(&state_dict, &["0", "2"]).unwrap();
+    let x = Vector3::new(-1., 0., 2.);
+    assert_eq!(mlp.evaluate(x, &Linear), linear.evaluate(x, &Linear));
+
+    // the widths must match the network
+    assert!(matches!(
+        DynNetwork::<f32, 4, 2>::from_dense_layers(&layers),
+        Err(DataError::WidthMismatch {
+            expected: 4,
+            found: 3,
+            ..
+        })
+    ));
+    assert!(matches!(
+        DynNetwork::<f32, 3, 2>::from_dense_layers(&layers[..1]),
+        Err(DataError::WidthMismatch {
+            expected: 2,
+            found: 4,
+            ..
+        })
+    ));
+    assert!(matches!(
+        Mlp::<f64>::from_state_dict(&state_dict, &["2", "0"]),
+        Err(DataError::WidthMismatch {
+            expected: 3,
+            found: 4,
+            ..
+        })
+    ));
+    assert!(matches!(
+        Mlp::<f64>::from_state_dict(&state_dict, &["0", "1"]),
+        Err(DataError::Format(_))
+    ));
+}
+
+/// Every activation should match PyTorch, not just ReLU
+#[test]
+fn activation_test() {
+    let state_dict = read_safetensors(safetensors().as_slice()).unwrap();
+    let x = [
+        Vector3::new(1., -2., 0.5),
+        Vector3::new(0.5, 1., -1.),
+        Vector3::new(-1., 0., 2.),
+    ];
+
+    // `model(x)` in PyTorch, with `nn.ReLU` replaced by each activation
+    for (activation, y) in [
+        (
+            Activation::Sigmoid,
+            [
+                Vector2::new(1.7097197909445834, -1.134078301508683),
+                Vector2::new(1.6674955731473178, -0.4481643553039738),
+                Vector2::new(2.0965216845727963, -0.7868455289534744),
+            ],
+        ),
+        (
+            Activation::LeakyRelu(0.125),
+            [
+                Vector2::new(2.1171875, -2.171875),
+                Vector2::new(0.8125, 0.671875),
+                Vector2::new(2.6171875, -1.265625),
+            ],
+        ),
+        (
+            Activation::Elu(0.5),
+            [
+                Vector2::new(1.7888375315688414, -2.2225905715242025),
+                Vector2::new(0.7453826649281583, 0.6383163324640793),
+                Vector2::new(2.5973500978839255, -1.3052998042321486),
+            ],
+        ),
+    ] {
+        let mut layers = state_dict_layers(&state_dict, &["0", "2"]).unwrap();
+        layers[0].activation = Some(activation);
+        let network = DynNetwork::<f64, 3, 2>::from_dense_layers(&layers).unwrap();
+
+        for (x, y) in x.iter().zip(y) {
+            assert!((network.evaluate(*x, &Linear) - y).norm() < 1e-12);
+        }
+    }
+}
@@ -23,7 +23,7 @@ fn improvement_test() {
 
     let mut opt = AdamOptimiser::default();
 
-    let first_loss = get_loss(data.iter(), &mut network, &activator, &squared_error);
+    let first_loss = get_loss(data.iter(), &network, &activator, &squared_error).unwrap();
 
     for _ in 0..10 {
         train(
@@ -32,10 +32,11 @@ fn improvement_test() {
             &activator,
             &squared_error,
             &mut opt,
-        );
+        )
+        .unwrap();
     }
 
-    let last_loss = get_loss(data.iter(), &mut network, &activator, &squared_error);
+    let last_loss = get_loss(data.iter(), &network, &activator, &squared_error).unwrap();
 
     assert!(first_loss > last_loss) // should have definitely got better
 }
@@ -0,0 +1,111 @@
+use nalgebra::{SMatrix, SVector};
+use network_macro::network;
+use neural_thingamajigy::{
+    operations::{LayerNorm, Softmax, StandardScaler},
+    transformer::TransformerEncoderBlock,
+    ChainableNetwork, Embedding, Layer, LayerInfo, NetworkInfo, RandomisableNetwork, SparseLayer,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+network!(pub Encoder, f32, 6, hidden: 4, 4, code: 2);
+network!(pub Head, f32, 2, logits: 3);
+
+/// Chains of networks and operations can describe every layer
+#[test]
+fn info_test() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let encoder = Encoder::random(&mut rng);
+    let head = Head::random(&mut rng);
+
+    let hidden: &Layer<f32, 6, 4> = encoder.hidden();
+    assert_eq!(hidden.info("hidden").parameters, 6 * 4 + 4);
+    let _: &Layer<f32, 4, 4> = encoder.layer1();
+    let _: &Layer<f32, 2, 3> = head.logits();
+
+    let scaler = StandardScaler::<f32, 6> {
+        mean: SVector::zeros(),
+        standard_deviation: SVector::repeat(1.),
+    };
+    let model = scaler
+        .chain(LayerNorm::<f32, 3>::new())
+        .chain(&encoder)
+        .chain(&head)
+        .chain(Softmax);
+
+    assert_eq!(model.layer_count(), 7);
+    assert_eq!(model.widths().collect::<Vec<_>>(), [6, 6, 6, 4, 4, 2, 3, 3]);
+    assert_eq!(
+        model.layer_info().map(|l| l.name).collect::<Vec<_>>(),
+        [
+            "standard_scaler",
+            "layer_norm",
+            "hidden",
+            "layer1",
+            "code",
+            "logits",
+            "softmax"
+        ]
+    );
+    assert_eq!(
+        model.layer_info().nth(1),
+        Some(LayerInfo {
+            name: "layer_norm",
+            kind: "LayerNorm",
+            inputs: 6,
+            outputs: 6,
+            parameters: 6,
+            trainable_parameters: 6,
+        })
+    );
+
+    let dense = (6 * 4 + 4) + (4 * 4 + 4) + (4 * 2 + 2) + (2 * 3 + 3);
+    assert_eq!(model.trainable_parameter_count(), 6 + dense);
+    assert_eq!(model.parameter_count(), 12 + 6 + dense);
+
+    let summary = model.summary().to_string();
+    assert_eq!(summary.lines().count(), 1 + 7 + 3);
+    assert!(summary.contains("Non-trainable parameters: 12"));
+}
+
+/// Every network and operation describes its parameters, not just dense layers
+#[test]
+fn parts_info_test() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    let embedding = Embedding::<f32, 10, 4>::new(SMatrix::zeros());
+    let info = NetworkInfo::<2, 8>::layer_info(&embedding).next().unwrap();
+    assert_eq!((info.kind, info.inputs, info.outputs), ("Embedding", 2, 8));
+    assert_eq!(info.trainable_parameters, 10 * 4);
+
+    let block = TransformerEncoderBlock::<f32, 2, 4, 2, 8>::random(&mut rng);
+    let attention = 4 * (4 * 4 + 4);
+    let feed_forward = (4 * 8 + 8) + (8 * 4 + 4);
+    assert_eq!(
+        NetworkInfo::<8, 8>::layer_info(&block)
+            .map(|l| (l.name, l.parameters))
+            .collect::<Vec<_>>(),
+        [
+            ("attention_norm", 2 * 4),
+            ("attention", attention),
+            ("feed_forward_norm", 2 * 4),
+            ("feed_forward", feed_forward),
+        ]
+    );
+    assert_eq!(
+        NetworkInfo::<8, 8>::trainable_parameter_count(&block),
+        4 * 4 + attention + feed_forward
+    );
+
+    // 2 of the 6 weights are stored, and borrowed weights can't be trained
+    let sparse = SparseLayer::<f32, 3, 2>::new(&[0, 1, 2], &[0, 2], &[1., 2.], &[0., 0.]);
+    assert_eq!(sparse.parameter_count(), 2 + 2);
+    assert_eq!(sparse.trainable_parameter_count(), 0);
+
+    let model = sparse.chain(Softmax);
+    assert_eq!(
+        model.layer_info().map(|l| l.kind).collect::<Vec<_>>(),
+        ["SparseLayer", "Softmax"]
+    );
+    assert_eq!(model.parameter_count(), 4);
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "parallel")]
+
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators, loss::squared_error, optimiser::AdamOptimiser, train, train_parallel, Network,
+    RandomisableNetwork,
+};
+use rand::rngs::mock::StepRng;
+
+network!(pub MyNetwork, f64, 2, 5, 5, 1);
+
+/// Splitting gradients across threads must not change the result
+#[test]
+fn parallel_matches_serial_test() {
+    let activator = activators::Relu {
+        leaky_gradient: 0.01,
+    };
+
+    // identical, deterministic starting points
+    let mut serial = MyNetwork::random(&mut StepRng::new(1, 1 << 60));
+    let mut parallel = MyNetwork::random(&mut StepRng::new(1, 1 << 60));
+
+    // enough samples to span several gradient chunks
+    let data: Vec<_> = (0..300)
+        .map(|i| {
+            let (a, b) = ((i % 17) as f64 / 17., (i % 5) as f64 / 5.);
+            (Vector2::new(a, b), Vector1::new(a * b))
+        })
+        .collect();
+
+    let mut serial_opt = AdamOptimiser::default();
+    let mut parallel_opt = AdamOptimiser::default();
+
+    for _ in 0..5 {
+        let serial_loss = train(
+            data.iter(),
+            &mut serial,
+            &activator,
+            &squared_error,
+            &mut serial_opt,
+        )
+        .unwrap();
+        let parallel_loss = train_parallel(
+            &data,
+            &mut parallel,
+            &activator,
+            &squared_error,
+            &mut parallel_opt,
+        )
+        .unwrap();
+
+        assert_eq!(serial_loss, parallel_loss);
+    }
+
+    for (x, _) in &data {
+        assert_eq!(
+            serial.evaluate(*x, &activator),
+            parallel.evaluate(*x, &activator)
+        );
+    }
+}
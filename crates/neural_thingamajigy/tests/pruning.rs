@@ -0,0 +1,64 @@
+use nalgebra::{Vector1, Vector2};
+use network_macro::network;
+use neural_thingamajigy::{
+    activators,
+    loss::squared_error,
+    optimiser::AdamOptimiser,
+    pruning::{prune, PrunableNetwork, PruneScope},
+    train, ChainableNetwork, Network, RandomisableNetwork,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+network!(pub MyNetwork, f64, 2, 8, 8, 1);
+
+/// Pruned weights should stay zero while fine-tuning, and the sparse export should match
+#[test]
+fn pruning_test() {
+    let activator = activators::Sigmoid;
+    let data = [
+        (Vector2::new(0., 0.), Vector1::new(0.)),
+        (Vector2::new(0., 1.), Vector1::new(1.)),
+        (Vector2::new(1., 0.), Vector1::new(1.)),
+        (Vector2::new(1., 1.), Vector1::new(0.)),
+    ];
+
+    let network = MyNetwork::random(&mut ChaCha8Rng::seed_from_u64(4));
+    let mut pruned = prune(network, 0.5, PruneScope::PerLayer);
+    // 8 of 16, 32 of 64 and 4 of 8 weights
+    assert_eq!(pruned.network.sparsity(), 44. / 88.);
+
+    let mut opt = AdamOptimiser::default();
+    for _ in 0..100 {
+        train(
+            data.iter(),
+            &mut pruned,
+            &activator,
+            &squared_error,
+            &mut opt,
+        )
+        .unwrap();
+    }
+    assert_eq!(pruned.network.sparsity(), 44. / 88.);
+
+    let network = pruned.into_inner();
+    let (layer0, layer1, layer2) = (
+        network.layer0.to_sparse(),
+        network.layer1.to_sparse(),
+        network.layer2.to_sparse(),
+    );
+    assert_eq!(layer1.values.len(), 32);
+
+    let sparse = layer0
+        .as_layer()
+        .chain(layer1.as_layer())
+        .chain(layer2.as_layer());
+    for (x, _) in &data {
+        let difference = network.evaluate(*x, &activator) - sparse.evaluate(*x, &activator);
+        assert!(difference.norm() < 1e-12);
+    }
+
+    // global pruning only counts as many weights as it needs to
+    let pruned = prune(network, 0.75, PruneScope::Global);
+    assert_eq!(pruned.network.sparsity(), 66. / 88.);
+}